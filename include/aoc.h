@@ -0,0 +1,41 @@
+/* Generated from src/ffi.rs, do not edit. */
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* success */
+#define AOC_OK 0
+/* a required pointer was null */
+#define AOC_ERR_NULL_POINTER -1
+/* the day is not implemented */
+#define AOC_ERR_UNKNOWN_DAY -2
+/* the part is not 1 or 2 */
+#define AOC_ERR_INVALID_PART -3
+/* the input is not valid UTF-8 */
+#define AOC_ERR_INVALID_UTF8 -4
+/* the input could not be parsed */
+#define AOC_ERR_PARSE -5
+/* the solver panicked */
+#define AOC_ERR_PANIC -6
+/* the output buffer is too small */
+#define AOC_ERR_BUFFER_TOO_SMALL -7
+
+/* Solves one part of one day, writing the NUL terminated answer to `out`.
+ * On AOC_ERR_PARSE and AOC_ERR_PANIC the error message is written instead. */
+int32_t aoc_solve(uint8_t day, uint8_t part, const uint8_t *input, size_t len,
+                  char *out, size_t out_len);
+
+/* A static description of an error code. */
+const char *aoc_strerror(int32_t code);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* AOC_H */
@@ -0,0 +1,29 @@
+/* Solves both parts of day 1's sample through the C ABI, and checks errors come back as codes.
+ *
+ * Built and run by the ignored `test_c_caller` test in src/ffi.rs. */
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static const char SAMPLE[] = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+
+static int solve(uint8_t day, uint8_t part, const char *input) {
+    char out[64];
+    int32_t code = aoc_solve(day, part, (const uint8_t *)input, strlen(input), out, sizeof out);
+    if (code == AOC_OK || code == AOC_ERR_PARSE) {
+        printf("day %u part %u: %s\n", day, part, out);
+    } else {
+        printf("day %u part %u: %s\n", day, part, aoc_strerror(code));
+    }
+    return code;
+}
+
+int main(void) {
+    int failed = 0;
+    failed |= solve(1, 1, SAMPLE) != AOC_OK;
+    failed |= solve(1, 2, SAMPLE) != AOC_OK;
+    failed |= solve(1, 1, "1\nx\n") != AOC_ERR_PARSE;
+    failed |= solve(99, 1, SAMPLE) != AOC_ERR_UNKNOWN_DAY;
+    return failed;
+}
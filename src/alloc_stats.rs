@@ -0,0 +1,199 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Allocation made while measuring, as reported by `measure`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Calls to `alloc` and `realloc`.
+    pub allocs: u64,
+    /// Bytes newly allocated: the whole size for `alloc`, but only the growth for `realloc`,
+    /// so a `Vec` grown by doubling to `n` bytes counts `n` rather than about `2n`.
+    pub bytes: u64,
+    /// Most bytes live at once beyond what was live when measuring started.
+    pub peak: u64,
+}
+
+/// The system allocator, counting what each thread allocates.
+///
+/// Installed as the global allocator by the `alloc-stats` feature; without it `measure`
+/// reports nothing. Counters are per thread so that solvers measured on the runner's
+/// worker threads do not see each other's allocations.
+///
+/// Frees are counted against the thread that frees the memory, not the one that allocated
+/// it, so the numbers are only meaningful for work done within one thread. Freeing memory
+/// allocated on another thread, such as a parsed input dropped after solving, can take a
+/// thread's live bytes negative, which is why a measured `peak` is never less than 0.
+pub struct CountingAlloc;
+
+#[derive(Clone, Copy)]
+struct Counters {
+    allocs: u64,
+    bytes: u64,
+    live: i64,
+    peak: i64,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters { allocs: 0, bytes: 0, live: 0, peak: 0 })
+    };
+}
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// Counts `calls` calls allocating `bytes` new bytes, after which `allocated` bytes replace
+/// `freed` ones.
+fn record(calls: u64, bytes: usize, allocated: usize, freed: usize) {
+    // the thread local may already be gone while a thread shuts down
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        c.allocs += calls;
+        c.bytes += bytes as u64;
+        c.live += allocated as i64 - freed as i64;
+        c.peak = c.peak.max(c.live);
+        counters.set(c);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        record(1, layout.size(), layout.size(), 0);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        record(1, layout.size(), layout.size(), 0);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(0, 0, 0, layout.size());
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let growth = new_size.saturating_sub(layout.size());
+        record(1, growth, new_size, layout.size());
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Whether `CountingAlloc` is the global allocator, so `measure` has something to report.
+pub fn enabled() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Runs `f`, returning what it allocated on this thread, or `None` if allocations are not
+/// being counted.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !enabled() {
+        return (f(), None);
+    }
+
+    // the peak is reset to measure `f` alone, then restored for any measurement around this one
+    let before = COUNTERS.with(|counters| {
+        let mut c = counters.get();
+        let before = c;
+        c.peak = c.live;
+        counters.set(c);
+        before
+    });
+    let result = f();
+    let after = COUNTERS.with(|counters| {
+        let mut c = counters.get();
+        let after = c;
+        c.peak = c.peak.max(before.peak);
+        counters.set(c);
+        after
+    });
+
+    let stats = AllocStats {
+        allocs: after.allocs - before.allocs,
+        bytes: after.bytes - before.bytes,
+        peak: (after.peak - before.live).max(0) as u64,
+    };
+    (result, Some(stats))
+}
+
+/// Formats a byte count with a binary unit, like `1.5 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn test_measure() {
+        let (sum, stats) = measure(|| {
+            let small = vec![0u8; 1000];
+            drop(small);
+            let big = vec![1u64; 1000];
+            big.iter().sum::<u64>()
+        });
+        assert_eq!(sum, 1000);
+
+        if cfg!(feature = "alloc-stats") {
+            let stats = stats.unwrap();
+            assert_eq!(stats.allocs, 2);
+            assert_eq!(stats.bytes, 9000);
+            assert_eq!(stats.peak, 8000);
+        } else {
+            assert_eq!(stats, None);
+        }
+    }
+
+    #[test]
+    fn test_nested_measure() {
+        let (inner, outer) = measure(|| {
+            let big = vec![0u8; 4000];
+            drop(big);
+            let (_, inner) = measure(|| drop(vec![0u8; 1000]));
+            inner
+        });
+
+        if cfg!(feature = "alloc-stats") {
+            assert_eq!(inner.unwrap().peak, 1000);
+            assert_eq!(outer.unwrap().peak, 4000);
+        }
+    }
+
+    #[test]
+    fn test_realloc_bytes() {
+        let (capacity, stats) = measure(|| {
+            let mut grown = Vec::<u8>::new();
+            for byte in 0..1000 {
+                grown.push(byte as u8);
+            }
+            grown.capacity()
+        });
+
+        if cfg!(feature = "alloc-stats") {
+            let stats = stats.unwrap();
+            assert_eq!(stats.bytes, capacity as u64);
+            assert!(stats.allocs > 1);
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::prelude::*;
+
+/// A puzzle answer, wide enough for any integer puzzle and able to hold text ones.
+///
+/// Integers compare by value regardless of variant, so `Answer::U64(5) == Answer::I128(5)`.
+#[derive(Debug, Clone, Eq)]
+pub enum Answer {
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+    Text(String),
+}
+
+impl Answer {
+    fn as_i128(&self) -> Option<i128> {
+        match *self {
+            Answer::I64(n) => Some(i128::from(n)),
+            Answer::U64(n) => Some(i128::from(n)),
+            Answer::I128(n) => Some(n),
+            Answer::U128(n) => i128::try_from(n).ok(),
+            Answer::Text(_) => None,
+        }
+    }
+
+    fn same(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (Answer::Text(_), _) | (_, Answer::Text(_)) => false,
+            (Answer::U128(a), Answer::U128(b)) => a == b,
+            _ => match (self.as_i128(), other.as_i128()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        self.same(other)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::I64(n) => write!(f, "{}", n),
+            Answer::U64(n) => write!(f, "{}", n),
+            Answer::I128(n) => write!(f, "{}", n),
+            Answer::U128(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Parses an expected answer: anything that reads as an integer is one, the rest is text.
+impl FromStr for Answer {
+    type Err = core::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let answer = if let Ok(n) = s.parse::<i64>() {
+            Answer::I64(n)
+        } else if let Ok(n) = s.parse::<u64>() {
+            Answer::U64(n)
+        } else if let Ok(n) = s.parse::<i128>() {
+            Answer::I128(n)
+        } else if let Ok(n) = s.parse::<u128>() {
+            Answer::U128(n)
+        } else {
+            Answer::Text(s.to_string())
+        };
+        Ok(answer)
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($variant:ident: $($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(n: $ty) -> Self {
+                    Answer::$variant(n.into())
+                }
+            }
+
+            impl PartialEq<$ty> for Answer {
+                fn eq(&self, other: &$ty) -> bool {
+                    self.same(&Answer::from(*other))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(I64: i8, i16, i32, i64);
+impl_from_integer!(U64: u8, u16, u32, u64);
+impl_from_integer!(I128: i128);
+impl_from_integer!(U128: u128);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::U64(n as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        match self {
+            Answer::Text(s) => s == other,
+            _ => other.parse::<Answer>().is_ok_and(|other| self.same(&other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integer_equality() {
+        assert_eq!(Answer::from(5u32), Answer::from(5i32));
+        assert_eq!(Answer::U64(5), Answer::I128(5));
+        assert_eq!(Answer::U128(u128::MAX), Answer::U128(u128::MAX));
+        assert_ne!(Answer::U128(u128::MAX), Answer::I128(-1));
+        assert_ne!(Answer::I64(-1), Answer::U64(u64::MAX));
+        assert_eq!(Answer::from(1594785890u32), 1594785890u32);
+        assert_eq!(Answer::from(-3i64), -3i64);
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(Answer::from("EFJKZLBL"), "EFJKZLBL");
+        assert_ne!(Answer::from("5"), Answer::from(5u32));
+        assert_eq!(Answer::from(5u32), "5");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("19571".parse::<Answer>().unwrap(), Answer::I64(19571));
+        assert_eq!("-7".parse::<Answer>().unwrap(), Answer::I64(-7));
+        assert_eq!(
+            "18446744073709551615".parse::<Answer>().unwrap(),
+            Answer::U64(u64::MAX)
+        );
+        assert_eq!(
+            "340282366920938463463374607431768211455"
+                .parse::<Answer>()
+                .unwrap(),
+            Answer::U128(u128::MAX)
+        );
+        assert_eq!(
+            "ABC".parse::<Answer>().unwrap(),
+            Answer::Text("ABC".to_string())
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::I128(-12).to_string(), "-12");
+        assert_eq!(Answer::Text("ok".to_string()).to_string(), "ok");
+    }
+}
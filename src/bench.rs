@@ -0,0 +1,301 @@
+use std::fmt;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use crate::{DynSolution, ParseError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part1 => write!(f, "part1"),
+            Phase::Part2 => write!(f, "part2"),
+        }
+    }
+}
+
+impl std::str::FromStr for Phase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            "part1" => Ok(Phase::Part1),
+            "part2" => Ok(Phase::Part2),
+            _ => Err(format!("unknown phase `{}`", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples to summarize");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        };
+
+        let secs = samples
+            .iter()
+            .map(Duration::as_secs_f64)
+            .collect::<Vec<_>>();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / secs.len() as f64;
+
+        Self {
+            mean: Duration::from_secs_f64(mean),
+            median,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub day: u8,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+/// Times parsing and each part of `day` separately, `runs` times each.
+pub fn bench_day(
+    day: &dyn DynSolution,
+    input: &str,
+    runs: usize,
+) -> Result<Vec<Measurement>, ParseError> {
+    let runs = runs.max(1);
+    let measurement = |phase, samples: &[Duration]| Measurement {
+        day: day.day(),
+        phase,
+        stats: Stats::from_samples(samples),
+    };
+
+    let mut samples = Vec::with_capacity(runs);
+    let mut parsed = day.parse(input)?;
+    for _ in 0..runs {
+        let start = Instant::now();
+        parsed = day.parse(input)?;
+        samples.push(start.elapsed());
+    }
+    let mut measurements = vec![measurement(Phase::Parse, &samples)];
+
+    for phase in [Phase::Part1, Phase::Part2] {
+        samples.clear();
+        for _ in 0..runs {
+            let start = Instant::now();
+            let answer = match phase {
+                Phase::Part1 => day.part1(parsed.as_ref()),
+                _ => day.part2(parsed.as_ref()),
+            };
+            samples.push(start.elapsed());
+            std::hint::black_box(answer);
+        }
+        measurements.push(measurement(phase, &samples));
+    }
+
+    Ok(measurements)
+}
+
+/// Saved measurements, one `day,phase,mean_ns,median_ns,stddev_ns` line each.
+pub fn to_csv(measurements: &[Measurement]) -> String {
+    let mut csv = String::from("day,phase,mean_ns,median_ns,stddev_ns\n");
+    for m in measurements {
+        writeln!(
+            csv,
+            "{},{},{},{},{}",
+            m.day,
+            m.phase,
+            m.stats.mean.as_nanos(),
+            m.stats.median.as_nanos(),
+            m.stats.stddev.as_nanos()
+        )
+        .unwrap();
+    }
+    csv
+}
+
+pub fn from_csv(text: &str) -> Result<Vec<Measurement>, String> {
+    let mut measurements = Vec::new();
+    for (idx, line) in text.lines().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let invalid = || format!("invalid benchmark line {}: `{}`", idx + 1, line);
+        let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+        let [day, phase, mean, median, stddev] = fields[..] else {
+            return Err(invalid());
+        };
+        let nanos = |field: &str| field.parse::<u64>().map(Duration::from_nanos);
+        measurements.push(Measurement {
+            day: day.parse().map_err(|_| invalid())?,
+            phase: phase.parse()?,
+            stats: Stats {
+                mean: nanos(mean).map_err(|_| invalid())?,
+                median: nanos(median).map_err(|_| invalid())?,
+                stddev: nanos(stddev).map_err(|_| invalid())?,
+            },
+        });
+    }
+    Ok(measurements)
+}
+
+/// How much slower than the baseline's mean, as a fraction, counts as a regression.
+pub const DEFAULT_THRESHOLD: f64 = 0.10;
+
+/// The mean's change relative to the same day and phase in `baseline`, e.g. `0.25` for 25%
+/// slower.
+pub fn change(measurement: &Measurement, baseline: &[Measurement]) -> Option<f64> {
+    let previous = baseline
+        .iter()
+        .find(|b| b.day == measurement.day && b.phase == measurement.phase)?;
+    let previous = previous.stats.mean.as_secs_f64();
+    if previous == 0.0 {
+        return None;
+    }
+    Some(measurement.stats.mean.as_secs_f64() / previous - 1.0)
+}
+
+pub fn render_table(
+    measurements: &[Measurement],
+    baseline: &[Measurement],
+    threshold: f64,
+) -> String {
+    let mut table = format!(
+        "{:<4} {:<6} {:>11} {:>11} {:>11} {:>9}\n",
+        "day", "phase", "mean", "median", "stddev", "change"
+    );
+    for m in measurements {
+        let change = match change(m, baseline) {
+            Some(change) if change > threshold => format!("{:+.1}% !", change * 100.0),
+            Some(change) => format!("{:+.1}%", change * 100.0),
+            None => String::new(),
+        };
+        let row = format!(
+            "{:<4} {:<6} {:>11} {:>11} {:>11} {:>9}",
+            format!("{:02}", m.day),
+            m.phase.to_string(),
+            format_duration(m.stats.mean),
+            format_duration(m.stats.median),
+            format_duration(m.stats.stddev),
+            change
+        );
+        table.push_str(row.trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+/// Measurements that got slower than `threshold` relative to `baseline`.
+pub fn regressions<'a>(
+    measurements: &'a [Measurement],
+    baseline: &[Measurement],
+    threshold: f64,
+) -> Vec<&'a Measurement> {
+    measurements
+        .iter()
+        .filter(|m| change(m, baseline).is_some_and(|change| change > threshold))
+        .collect()
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_secs_f64() * 1_000_000.0;
+    if micros < 1_000.0 {
+        format!("{:.1}µs", micros)
+    } else if micros < 1_000_000.0 {
+        format!("{:.2}ms", micros / 1_000.0)
+    } else {
+        format!("{:.2}s", micros / 1_000_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    fn measurement(day: u8, phase: Phase, mean: u64) -> Measurement {
+        Measurement {
+            day,
+            phase,
+            stats: Stats {
+                mean: ms(mean),
+                median: ms(mean),
+                stddev: ms(0),
+            },
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&[ms(2), ms(4), ms(4), ms(4), ms(5), ms(5), ms(7), ms(9)]);
+        assert_eq!(stats.mean, ms(5));
+        assert_eq!(stats.median, ms(4) + Duration::from_micros(500));
+        assert_eq!(stats.stddev, ms(2));
+
+        let stats = Stats::from_samples(&[ms(3), ms(1), ms(2)]);
+        assert_eq!(stats.median, ms(2));
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let measurements = vec![
+            measurement(1, Phase::Parse, 3),
+            measurement(5, Phase::Part2, 150),
+        ];
+        let csv = to_csv(&measurements);
+        assert!(csv.starts_with("day,phase,mean_ns,median_ns,stddev_ns\n1,parse,3000000,"));
+        assert_eq!(from_csv(&csv).unwrap(), measurements);
+        assert!(from_csv("header\n1,parse,3").is_err());
+        assert!(from_csv("header\n1,part3,1,1,1").is_err());
+    }
+
+    #[test]
+    fn test_regressions() {
+        let baseline = vec![
+            measurement(5, Phase::Part1, 100),
+            measurement(5, Phase::Part2, 100),
+        ];
+        let current = vec![
+            measurement(5, Phase::Part1, 105),
+            measurement(5, Phase::Part2, 150),
+            measurement(6, Phase::Part1, 150),
+        ];
+        let slower = regressions(&current, &baseline, DEFAULT_THRESHOLD);
+        assert_eq!(slower, vec![&current[1]]);
+
+        let table = render_table(&current, &baseline, DEFAULT_THRESHOLD);
+        assert!(table.contains("+50.0% !"));
+        assert!(table.contains("+5.0%\n"));
+    }
+
+    #[test]
+    fn test_bench_day() {
+        let day = crate::solution(1).unwrap();
+        let measurements = bench_day(day, "1\n2\n3\n4", 3).unwrap();
+        let phases = measurements.iter().map(|m| m.phase).collect::<Vec<_>>();
+        assert_eq!(phases, vec![Phase::Parse, Phase::Part1, Phase::Part2]);
+        assert!(bench_day(day, "x", 3).is_err());
+    }
+}
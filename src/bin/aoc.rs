@@ -0,0 +1,759 @@
+use std::io::{BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use advent_of_code_2021::alloc_stats::{format_bytes, AllocStats};
+use advent_of_code_2021::bench::{self, format_duration};
+use advent_of_code_2021::client::{Client, Download};
+use advent_of_code_2021::compare::{self, SLOW_FACTOR};
+use advent_of_code_2021::config::Config;
+use advent_of_code_2021::examples;
+use advent_of_code_2021::fuzz;
+use advent_of_code_2021::input::{InputError, InputLoader};
+use advent_of_code_2021::manifest::Manifest;
+use advent_of_code_2021::repl::Repl;
+use advent_of_code_2021::report::{self, Format};
+use advent_of_code_2021::runner::{Failure, Job, Runner};
+use advent_of_code_2021::scaffold;
+use advent_of_code_2021::submit::{self, History, Outcome};
+use advent_of_code_2021::verify::{bless, check_jobs, verify_days, Status};
+use advent_of_code_2021::watch::{self, Snapshot, Watcher};
+use advent_of_code_2021::{solution, DynSolution, ParseError, SOLUTIONS};
+
+const USAGE: &str = "usage:
+    aoc run --day <N> [--part <1|2>] [--input <PATH|->] [--user <NAME>] [--explain]
+    aoc run --all [--user <NAME>] [--jobs <N>] [--explain]
+    aoc run (--day <N> | --all) --format <json|csv> [--examples] [--jobs <N>] [--manifest <PATH>]
+    aoc compare --day <N> [--dir <PATH>]
+    aoc watch --day <N> [--user <NAME>] [--interval <MS>]
+    aoc repl [--day <N>] [--user <NAME>]
+    aoc verify [--day <N>] [--jobs <N>] [--bless] [--manifest <PATH>]
+    aoc download --day <N> [--user <NAME>]
+    aoc submit --day <N> --part <1|2> [--user <NAME>]
+    aoc new <DAY> [--title <TITLE>]
+    aoc bench [--day <N>] [--input <PATH|->] [--user <NAME>] [--runs <N>] [--save <PATH>]
+              [--baseline <PATH>] [--threshold <PERCENT>]
+    aoc fuzz [--day <N>] [--runs <N>] [--seed <N>]
+
+inputs default to `input/[<user>/]day_XX.txt`; AOC_INPUT_DIR and AOC_USER override them";
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match run(&args) {
+        Ok(()) => {}
+        Err(CliError::Usage(message)) => {
+            eprintln!("error: {}", message);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+        Err(CliError::Failed(message)) => {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+    }
+}
+
+#[derive(Debug)]
+enum CliError {
+    Usage(String),
+    Failed(String),
+}
+
+impl From<String> for CliError {
+    fn from(message: String) -> Self {
+        CliError::Usage(message)
+    }
+}
+
+impl From<&str> for CliError {
+    fn from(message: &str) -> Self {
+        CliError::Usage(message.to_string())
+    }
+}
+
+impl From<InputError> for CliError {
+    fn from(error: InputError) -> Self {
+        CliError::Failed(format!("error: {}", error))
+    }
+}
+
+impl From<ParseError> for CliError {
+    fn from(error: ParseError) -> Self {
+        CliError::Failed(error.render())
+    }
+}
+
+fn run(args: &[String]) -> Result<(), CliError> {
+    let (command, rest) = args.split_first().ok_or("missing command")?;
+    match command.as_str() {
+        "run" => run_command(&Options::parse(rest)?),
+        "verify" => verify_command(&Options::parse(rest)?),
+        "bench" => bench_command(&Options::parse(rest)?),
+        "download" => download_command(&Options::parse(rest)?),
+        "submit" => submit_command(&Options::parse(rest)?),
+        "watch" => watch_command(&Options::parse(rest)?),
+        "compare" => compare_command(&Options::parse(rest)?),
+        "repl" => repl_command(&Options::parse(rest)?),
+        "fuzz" => fuzz_command(&Options::parse(rest)?),
+        "new" => {
+            let (day, rest) = rest.split_first().ok_or("`new` needs a day")?;
+            let day = parse_number("new", Some(day))?;
+            new_command(day, &Options::parse(rest)?)
+        }
+        _ => Err(format!("unknown command `{}`", command).into()),
+    }
+}
+
+#[derive(Debug, Default)]
+struct Options {
+    day: Option<u8>,
+    part: Option<u8>,
+    input: Option<String>,
+    dir: Option<String>,
+    user: Option<String>,
+    manifest: Option<String>,
+    runs: Option<usize>,
+    seed: Option<u64>,
+    jobs: Option<usize>,
+    save: Option<String>,
+    baseline: Option<String>,
+    threshold: Option<f64>,
+    title: Option<String>,
+    format: Option<Format>,
+    interval: Option<u64>,
+    all: bool,
+    examples: bool,
+    explain: bool,
+    bless: bool,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--day" => options.day = Some(parse_number(arg, args.next())?),
+                "--part" => {
+                    let part = parse_number(arg, args.next())?;
+                    if part != 1 && part != 2 {
+                        return Err(format!("part must be 1 or 2, got {}", part));
+                    }
+                    options.part = Some(part);
+                }
+                "--input" => options.input = Some(value(arg, args.next())?.clone()),
+                "--dir" => options.dir = Some(value(arg, args.next())?.clone()),
+                "--user" => options.user = Some(value(arg, args.next())?.clone()),
+                "--runs" => options.runs = Some(parse_number(arg, args.next())?),
+                "--seed" => options.seed = Some(parse_number(arg, args.next())?),
+                "--jobs" => {
+                    let jobs = parse_number(arg, args.next())?;
+                    if jobs == 0 {
+                        return Err("`--jobs` must be at least 1".to_string());
+                    }
+                    options.jobs = Some(jobs);
+                }
+                "--save" => options.save = Some(value(arg, args.next())?.clone()),
+                "--baseline" => options.baseline = Some(value(arg, args.next())?.clone()),
+                "--threshold" => options.threshold = Some(parse_number(arg, args.next())?),
+                "--format" => options.format = Some(value(arg, args.next())?.parse()?),
+                "--interval" => options.interval = Some(parse_number(arg, args.next())?),
+                "--examples" => options.examples = true,
+                "--explain" => options.explain = true,
+                "--title" => options.title = Some(value(arg, args.next())?.clone()),
+                "--manifest" => options.manifest = Some(value(arg, args.next())?.clone()),
+                "--all" => options.all = true,
+                "--bless" => options.bless = true,
+                _ => return Err(format!("unknown argument `{}`", arg)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn value<'a>(flag: &str, value: Option<&'a String>) -> Result<&'a String, String> {
+    value.ok_or_else(|| format!("`{}` needs a value", flag))
+}
+
+fn parse_number<T: FromStr>(flag: &str, arg: Option<&String>) -> Result<T, String> {
+    let arg = value(flag, arg)?;
+    arg.parse()
+        .map_err(|_| format!("`{}` expects a number, got `{}`", flag, arg))
+}
+
+fn run_command(options: &Options) -> Result<(), CliError> {
+    let parts = match options.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let days = if options.all {
+        if options.day.is_some() || options.input.is_some() {
+            return Err("`--all` cannot be combined with `--day` or `--input`".into());
+        }
+        SOLUTIONS.to_vec()
+    } else {
+        let day = options.day.ok_or("either `--day` or `--all` is required")?;
+        vec![solution(day).ok_or_else(|| format!("day {} is not implemented", day))?]
+    };
+
+    if let Some(format) = options.format {
+        return report_command(&days, &parts, format, options);
+    }
+
+    // a day whose input cannot be read or parsed is reported and the rest still run
+    let jobs = days
+        .iter()
+        .map(|&day| Job {
+            day,
+            name: input_name(options),
+            input: read_input(day, options).map_err(|error| match error {
+                CliError::Usage(message) | CliError::Failed(message) => message,
+            }),
+            parts: parts.clone(),
+        })
+        .collect::<Vec<_>>();
+    let runs = runner(options).explain(options.explain).run(&jobs);
+
+    let mut failed = 0;
+    let mut panicked = 0;
+    for (job, run) in jobs.iter().zip(&runs) {
+        println!("Day {:02}: {}", job.day.day(), job.day.title());
+        match &run.parsed {
+            Ok(()) => println!(
+                "  parse:  {}{}",
+                format_duration(run.parse_time),
+                alloc_note(run.parse_alloc)
+            ),
+            Err(failure) => {
+                match failure {
+                    Failure::Parse(error) => eprintln!("{}", error.render()),
+                    Failure::Input(message) => eprintln!("{}", message),
+                    Failure::Panic(_) => eprintln!("error: {}", failure),
+                }
+                failed += 1;
+                continue;
+            }
+        }
+
+        for part in &run.parts {
+            match &part.answer {
+                Ok(answer) => println!(
+                    "  part {}: {} ({}{})",
+                    part.part,
+                    answer,
+                    format_duration(part.time),
+                    alloc_note(part.alloc)
+                ),
+                Err(failure) => {
+                    println!("  part {}: {}", part.part, failure);
+                    panicked += 1;
+                }
+            }
+            for event in &part.events {
+                println!("    - {}", event);
+            }
+        }
+    }
+
+    let mut problems = Vec::new();
+    if failed > 0 {
+        problems.push(format!("{} day(s) failed", failed));
+    }
+    if panicked > 0 {
+        problems.push(format!("{} part(s) panicked", panicked));
+    }
+    if !problems.is_empty() {
+        return Err(CliError::Failed(problems.join(", ")));
+    }
+    Ok(())
+}
+
+/// Allocation counts to follow a timing, if the binary was built with `alloc-stats`.
+fn alloc_note(stats: Option<AllocStats>) -> String {
+    stats.map_or(String::new(), |s| {
+        format!(
+            ", {} allocs, {}, peak {}",
+            s.allocs,
+            format_bytes(s.bytes),
+            format_bytes(s.peak)
+        )
+    })
+}
+
+fn runner(options: &Options) -> Runner {
+    match options.jobs {
+        Some(jobs) => Runner::new().jobs(jobs),
+        None => Runner::new(),
+    }
+}
+
+/// What the input of a run is called in reports: the user, or the file it was read from.
+fn input_name(options: &Options) -> String {
+    match options.input.as_deref() {
+        Some("-") => "stdin".to_string(),
+        Some(path) => Path::new(path)
+            .file_stem()
+            .map_or(path.to_string(), |stem| stem.to_string_lossy().into_owned()),
+        None => loader(options).input_name().to_string(),
+    }
+}
+
+fn report_command(
+    days: &[&dyn DynSolution],
+    parts: &[u8],
+    format: Format,
+    options: &Options,
+) -> Result<(), CliError> {
+    let manifest = Manifest::load(&manifest_path(options))
+        .map_err(|e| CliError::Failed(format!("error: {}", e)))?;
+    let jobs = days
+        .iter()
+        .map(|&day| Job {
+            day,
+            name: input_name(options),
+            input: read_input(day, options).map_err(|e| match e {
+                CliError::Usage(message) | CliError::Failed(message) => message,
+            }),
+            parts: parts.to_vec(),
+        })
+        .collect::<Vec<_>>();
+    let results = check_jobs(&runner(options), &jobs, &manifest);
+
+    let mut checks = Vec::new();
+    for day in days {
+        if options.examples {
+            let examples =
+                examples::check(*day).map_err(|e| CliError::Failed(format!("error: {}", e)))?;
+            checks.extend(
+                examples
+                    .into_iter()
+                    .filter(|check| parts.contains(&check.part)),
+            );
+        }
+        checks.extend(
+            results
+                .iter()
+                .filter(|check| check.day == day.day())
+                .cloned(),
+        );
+    }
+
+    print!("{}", report::render(format, &checks));
+    Ok(())
+}
+
+fn compare_command(options: &Options) -> Result<(), CliError> {
+    let day = options.day.ok_or("`--day` is required")?;
+    let day = solution(day).ok_or_else(|| format!("day {} is not implemented", day))?;
+    let inputs = match &options.dir {
+        Some(dir) => compare::inputs_in(Path::new(dir)),
+        None => compare::discover(&loader(options), day.day()),
+    };
+    if inputs.is_empty() {
+        return Err(CliError::Failed(format!(
+            "no inputs for day {} to compare",
+            day.day()
+        )));
+    }
+
+    let rows = compare::compare(day, inputs);
+
+    println!("Day {:02}: {}", day.day(), day.title());
+    print!("{}", compare::render_table(&rows, SLOW_FACTOR));
+
+    let panicked = rows.iter().filter(|row| row.panicked()).count();
+    if panicked > 0 {
+        return Err(CliError::Failed(format!(
+            "{} input(s) made the solver panic",
+            panicked
+        )));
+    }
+    Ok(())
+}
+
+fn watch_command(options: &Options) -> Result<(), CliError> {
+    let day = options.day.ok_or("`--day` is required")?;
+    let interval = Duration::from_millis(options.interval.unwrap_or(500));
+    let root = scaffold::crate_root();
+    let input = loader(options).path(day);
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+
+    let mut watcher = Watcher::new();
+    let mut previous: Option<Snapshot> = None;
+    loop {
+        if watcher.changed(&watch::watched_paths(&root, &input, day)) {
+            println!("--- day {:02} ---", day);
+            let mut command = Command::new(&cargo);
+            command
+                .current_dir(&root)
+                .args(["run", "--quiet", "--bin", "aoc", "--", "run"])
+                .args(["--day", &day.to_string(), "--format", "csv", "--examples"]);
+            if let Some(user) = &options.user {
+                command.args(["--user", user]);
+            }
+
+            match command.output() {
+                Err(e) => eprintln!("error: failed to run {}: {}", cargo, e),
+                Ok(output) if !output.status.success() => {
+                    eprint!("{}", String::from_utf8_lossy(&output.stderr));
+                }
+                Ok(output) => {
+                    match watch::snapshot_from_csv(&String::from_utf8_lossy(&output.stdout)) {
+                        Err(e) => eprintln!("error: {}", e),
+                        Ok(snapshot) => {
+                            for ((input, part), answer) in &snapshot {
+                                println!("  {:<12} part {}: {}", input, part, answer);
+                            }
+                            if let Some(previous) = &previous {
+                                for change in watch::diff(previous, &snapshot) {
+                                    println!(
+                                        "  changed {} part {}: {} -> {}",
+                                        change.input,
+                                        change.part,
+                                        change.before.as_deref().unwrap_or("-"),
+                                        change.after.as_deref().unwrap_or("-")
+                                    );
+                                }
+                            }
+                            previous = Some(snapshot);
+                        }
+                    }
+                }
+            }
+        }
+        thread::sleep(interval);
+    }
+}
+
+fn repl_command(options: &Options) -> Result<(), CliError> {
+    let mut repl = Repl::new(loader(options));
+    if let Some(day) = options.day {
+        repl.eval(&format!("load {}", day))?;
+    }
+
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("{}", repl.prompt());
+        std::io::stdout().flush().ok();
+
+        let line = match lines.next() {
+            Some(line) => {
+                line.map_err(|e| CliError::Failed(format!("failed to read stdin: {}", e)))?
+            }
+            None => break,
+        };
+        match line.trim() {
+            "quit" | "exit" => break,
+            line => match repl.eval(line) {
+                Ok(output) if output.is_empty() => {}
+                Ok(output) => println!("{}", output),
+                Err(message) => eprintln!("{}", message),
+            },
+        }
+    }
+    println!();
+    Ok(())
+}
+
+fn fuzz_command(options: &Options) -> Result<(), CliError> {
+    let days = match options.day {
+        Some(day) => vec![solution(day).ok_or_else(|| format!("day {} is not implemented", day))?],
+        None => SOLUTIONS.to_vec(),
+    };
+    let runs = options.runs.unwrap_or(10_000);
+    let seed = options.seed.unwrap_or(0);
+    let loader = loader(options);
+
+    let mut crashed = 0;
+    for day in days {
+        let corpus = fuzz::seed_corpus(day.day(), &loader);
+        let start = std::time::Instant::now();
+        let crashes = fuzz::fuzz(day, &corpus, seed, runs);
+        println!("Day {:02}: {}", day.day(), day.title());
+        println!(
+            "  {} runs from {} inputs, {} crash(es) ({})",
+            runs,
+            corpus.len(),
+            crashes.len(),
+            format_duration(start.elapsed())
+        );
+        for crash in &crashes {
+            let path = fuzz::save(day.day(), crash)
+                .map_err(|e| CliError::Failed(format!("failed to save crash: {}", e)))?;
+            println!("  {}", crash.message);
+            println!("    saved to `{}`", path.display());
+        }
+        crashed += crashes.len();
+    }
+
+    if crashed > 0 {
+        return Err(CliError::Failed(format!("{} crash(es) found", crashed)));
+    }
+    Ok(())
+}
+
+fn manifest_path(options: &Options) -> PathBuf {
+    options
+        .manifest
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(Manifest::default_path)
+}
+
+fn verify_command(options: &Options) -> Result<(), CliError> {
+    let days = match options.day {
+        Some(day) => vec![solution(day).ok_or_else(|| format!("day {} is not implemented", day))?],
+        None => SOLUTIONS.to_vec(),
+    };
+    let path = manifest_path(options);
+    let mut manifest =
+        Manifest::load(&path).map_err(|e| CliError::Failed(format!("error: {}", e)))?;
+    let loader = InputLoader::from_env();
+
+    let checks = verify_days(&runner(options), &days, &loader, &manifest);
+
+    let mut failed = 0;
+    for check in &checks {
+        let status = match (check.status(), &check.actual) {
+            (Status::Pass, Ok(answer)) => format!("ok       {}", answer),
+            (Status::Unrecorded, Ok(answer)) => format!("new      {}", answer),
+            (Status::Fail, Ok(answer)) => {
+                let expected = check.expected.as_ref().unwrap();
+                format!("FAIL     {} (expected {})", answer, expected)
+            }
+            (_, Err(e)) => format!("ERROR    {}", e),
+            (_, Ok(answer)) => answer.to_string(),
+        };
+        if matches!(check.status(), Status::Fail | Status::Error) {
+            failed += 1;
+        }
+        println!(
+            "day {:02} part {} {:<12} {}",
+            check.day, check.part, check.input, status
+        );
+    }
+
+    if options.bless {
+        bless(&mut manifest, &checks);
+        manifest
+            .save(&path)
+            .map_err(|e| CliError::Failed(format!("error: {}", e)))?;
+        println!("recorded answers in `{}`", path.display());
+        return Ok(());
+    }
+
+    if failed > 0 {
+        return Err(CliError::Failed(format!("{} check(s) failed", failed)));
+    }
+    Ok(())
+}
+
+fn bench_command(options: &Options) -> Result<(), CliError> {
+    let days = match options.day {
+        Some(day) => vec![solution(day).ok_or_else(|| format!("day {} is not implemented", day))?],
+        None => SOLUTIONS.to_vec(),
+    };
+    let runs = options.runs.unwrap_or(100);
+    let threshold = options
+        .threshold
+        .map(|percent| percent / 100.0)
+        .unwrap_or(bench::DEFAULT_THRESHOLD);
+    let baseline = match &options.baseline {
+        Some(path) => bench::from_csv(&read_file(path)?)
+            .map_err(|e| CliError::Failed(format!("error: {}", e)))?,
+        None => Vec::new(),
+    };
+
+    let mut measurements = Vec::new();
+    for day in days {
+        let input = read_input(day, options)?;
+        measurements.extend(bench::bench_day(day, &input, runs)?);
+    }
+
+    print!(
+        "{}",
+        bench::render_table(&measurements, &baseline, threshold)
+    );
+
+    if let Some(path) = &options.save {
+        std::fs::write(path, bench::to_csv(&measurements))
+            .map_err(|e| CliError::Failed(format!("failed to write `{}`: {}", path, e)))?;
+        println!("saved results to `{}`", path);
+    }
+
+    let regressions = bench::regressions(&measurements, &baseline, threshold);
+    if !regressions.is_empty() {
+        return Err(CliError::Failed(format!(
+            "{} phase(s) regressed by more than {:.0}%",
+            regressions.len(),
+            threshold * 100.0
+        )));
+    }
+    Ok(())
+}
+
+fn new_command(day: u8, options: &Options) -> Result<(), CliError> {
+    let title = options
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Day {}", day));
+    let written = scaffold::new_day(&scaffold::crate_root(), day, &title)
+        .map_err(|e| CliError::Failed(format!("error: {}", e)))?;
+    for path in written {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn download_command(options: &Options) -> Result<(), CliError> {
+    let day = options.day.ok_or("`--day` is required")?;
+    let failed = |e: &dyn std::fmt::Display| CliError::Failed(format!("error: {}", e));
+    let config = Config::from_env().map_err(|e| failed(&e))?;
+    let client = Client::from_config(&config).map_err(|e| failed(&e))?;
+    match client
+        .download(&loader(options), day)
+        .map_err(|e| failed(&e))?
+    {
+        Download::Cached(path) => println!("already cached at {}", path.display()),
+        Download::Fetched(path) => println!("saved {}", path.display()),
+    }
+    Ok(())
+}
+
+fn submit_command(options: &Options) -> Result<(), CliError> {
+    let day = options.day.ok_or("`--day` is required")?;
+    let part = options.part.ok_or("`--part` is required")?;
+    let day = solution(day).ok_or_else(|| format!("day {} is not implemented", day))?;
+    let failed = |e: &dyn std::fmt::Display| CliError::Failed(format!("error: {}", e));
+
+    let loader = loader(options);
+    let parsed = day.parse(&loader.load(day.day())?)?;
+    let answer = match part {
+        1 => day.part1(parsed.as_ref()),
+        _ => day.part2(parsed.as_ref()),
+    };
+    println!("day {:02} part {}: submitting {}", day.day(), part, answer);
+
+    let config = Config::from_env().map_err(|e| failed(&e))?;
+    let client = Client::from_config(&config).map_err(|e| failed(&e))?;
+    let path = History::default_path();
+    let mut history = History::load(&path).map_err(|e| failed(&e))?;
+    let outcome = submit::submit(
+        &client,
+        &mut history,
+        loader.input_name(),
+        day.day(),
+        part,
+        &answer,
+        submit::now(),
+    )
+    .map_err(|e| failed(&e))?;
+    history.save(&path).map_err(|e| failed(&e))?;
+
+    let wait = history
+        .submissions()
+        .last()
+        .map_or(0, |s| s.wait_until.saturating_sub(s.time));
+    let message = match outcome {
+        Outcome::Correct => "that's the right answer".to_string(),
+        Outcome::Wrong => "that's not the right answer".to_string(),
+        Outcome::TooHigh => "that's not the right answer, it is too high".to_string(),
+        Outcome::TooLow => "that's not the right answer, it is too low".to_string(),
+        Outcome::Wait => "answered too recently, nothing was checked".to_string(),
+        Outcome::WrongLevel => "that part is already solved or not unlocked yet".to_string(),
+    };
+    if wait > 0 {
+        println!("{} (wait {}s before submitting again)", message, wait);
+    } else {
+        println!("{}", message);
+    }
+
+    match outcome {
+        Outcome::Correct => Ok(()),
+        _ => Err(CliError::Failed(format!("submission was {}", outcome))),
+    }
+}
+
+fn loader(options: &Options) -> InputLoader {
+    let loader = InputLoader::from_env();
+    match &options.user {
+        Some(user) => loader.with_user(user.as_str()),
+        None => loader,
+    }
+}
+
+fn read_input(day: &dyn DynSolution, options: &Options) -> Result<String, CliError> {
+    match options.input.as_deref() {
+        Some("-") => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| CliError::Failed(format!("failed to read stdin: {}", e)))?;
+            Ok(input)
+        }
+        Some(path) => read_file(path),
+        None => Ok(loader(options).load(day.day())?),
+    }
+}
+
+fn read_file(path: &str) -> Result<String, CliError> {
+    std::fs::read_to_string(path)
+        .map_err(|e| CliError::Failed(format!("failed to read `{}`: {}", path, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let options =
+            Options::parse(&args(&["--day", "5", "--part", "2", "--input", "-"])).unwrap();
+        assert_eq!(options.day, Some(5));
+        assert_eq!(options.part, Some(2));
+        assert_eq!(options.input.as_deref(), Some("-"));
+        assert!(!options.all);
+        assert_eq!(options.user, None);
+
+        let options = Options::parse(&args(&["--all", "--user", "bob"])).unwrap();
+        assert_eq!(options.user.as_deref(), Some("bob"));
+
+        assert!(Options::parse(&args(&["--all"])).unwrap().all);
+        assert!(Options::parse(&args(&["--bless"])).unwrap().bless);
+        assert_eq!(
+            Options::parse(&args(&["--format", "csv"])).unwrap().format,
+            Some(Format::Csv)
+        );
+        assert!(Options::parse(&args(&["--format", "xml"])).is_err());
+
+        let options = Options::parse(&args(&["--runs", "20", "--threshold", "12.5"])).unwrap();
+        assert_eq!(options.runs, Some(20));
+        assert_eq!(options.threshold, Some(12.5));
+        assert!(Options::parse(&args(&["--part", "3"])).is_err());
+        assert_eq!(
+            Options::parse(&args(&["--jobs", "3"])).unwrap().jobs,
+            Some(3)
+        );
+        assert!(Options::parse(&args(&["--jobs", "0"])).is_err());
+        assert!(Options::parse(&args(&["--day"])).is_err());
+        assert!(Options::parse(&args(&["--day", "x"])).is_err());
+    }
+
+    #[test]
+    fn test_run_all_continues() {
+        let options = Options::parse(&args(&["--all", "--user", "no-such-user"])).unwrap();
+        let Err(CliError::Failed(message)) = run_command(&options) else {
+            panic!("expected every day to fail");
+        };
+        assert_eq!(message, format!("{} day(s) failed", SOLUTIONS.len()));
+    }
+}
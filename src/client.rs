@@ -0,0 +1,216 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::http::{HttpBackend, HttpError, Method, Request, StdHttp};
+use crate::input::InputLoader;
+use crate::Answer;
+
+const USER_AGENT: &str = "github.com/cLazyZombie/advent-of-code-2021";
+
+#[derive(Debug)]
+pub enum ClientError {
+    NoSession,
+    Http(HttpError),
+    Status { status: u16, body: String },
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::NoSession => write!(
+                f,
+                "no session token; set `session` in .aoc.toml or the AOC_SESSION variable"
+            ),
+            ClientError::Http(e) => write!(f, "{}", e),
+            ClientError::Status { status, body } => {
+                let body = body.trim();
+                let body = body.get(..200).unwrap_or(body);
+                write!(f, "server answered {}: {}", status, body)
+            }
+            ClientError::Io(path, e) => write!(f, "failed to access `{}`: {}", path.display(), e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<HttpError> for ClientError {
+    fn from(error: HttpError) -> Self {
+        ClientError::Http(error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Download {
+    /// The input was already on disk and was not fetched again.
+    Cached(PathBuf),
+    Fetched(PathBuf),
+}
+
+/// Talks to the puzzle site as the configured user.
+pub struct Client<B = StdHttp> {
+    backend: B,
+    base_url: String,
+    year: u16,
+    session: String,
+}
+
+impl Client {
+    pub fn from_config(config: &Config) -> Result<Self, ClientError> {
+        Self::with_backend(StdHttp, config)
+    }
+}
+
+impl<B: HttpBackend> Client<B> {
+    pub fn with_backend(backend: B, config: &Config) -> Result<Self, ClientError> {
+        let session = config.session.clone().ok_or(ClientError::NoSession)?;
+        Ok(Self {
+            backend,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            year: config.year,
+            session,
+        })
+    }
+
+    pub fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day)
+    }
+
+    pub(crate) fn request(&self, method: Method, url: String) -> Request {
+        Request::new(method, url)
+            .header("Cookie", &format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    pub fn fetch_input(&self, day: u8) -> Result<String, ClientError> {
+        let request = self.request(Method::Get, format!("{}/input", self.day_url(day)));
+        let response = self.backend.send(&request)?;
+        if response.status != 200 {
+            return Err(ClientError::Status {
+                status: response.status,
+                body: response.body,
+            });
+        }
+        Ok(response.body)
+    }
+
+    /// Posts an answer and returns the page the site answered with.
+    pub fn post_answer(&self, day: u8, part: u8, answer: &Answer) -> Result<String, ClientError> {
+        let request = self
+            .request(Method::Post, format!("{}/answer", self.day_url(day)))
+            .form(&[
+                ("level", &part.to_string()),
+                ("answer", &answer.to_string()),
+            ]);
+        let response = self.backend.send(&request)?;
+        if response.status != 200 {
+            return Err(ClientError::Status {
+                status: response.status,
+                body: response.body,
+            });
+        }
+        Ok(response.body)
+    }
+
+    /// Saves `day`'s input where `loader` looks for it, unless a non-empty one is already there.
+    pub fn download(&self, loader: &InputLoader, day: u8) -> Result<Download, ClientError> {
+        let path = loader.path(day);
+        if std::fs::metadata(&path).is_ok_and(|meta| meta.len() > 0) {
+            return Ok(Download::Cached(path));
+        }
+
+        let input = self.fetch_input(day)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| ClientError::Io(parent.to_path_buf(), e))?;
+        }
+        std::fs::write(&path, input).map_err(|e| ClientError::Io(path.clone(), e))?;
+        Ok(Download::Fetched(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::tests::serve;
+    use crate::http::Response;
+    use std::cell::RefCell;
+
+    fn config(base_url: &str) -> Config {
+        Config {
+            session: Some("s3cr3t".to_string()),
+            base_url: base_url.to_string(),
+            year: 2021,
+        }
+    }
+
+    struct Recorder(RefCell<Vec<Request>>);
+
+    impl HttpBackend for Recorder {
+        fn send(&self, request: &Request) -> Result<Response, HttpError> {
+            self.0.borrow_mut().push(request.clone());
+            Ok(Response {
+                status: 200,
+                body: "1\n2\n".to_string(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_fetch_input_request() {
+        let client =
+            Client::with_backend(Recorder(RefCell::new(Vec::new())), &config("http://aoc/"))
+                .unwrap();
+        assert_eq!(client.fetch_input(6).unwrap(), "1\n2\n");
+
+        let requests = client.backend().0.borrow();
+        assert_eq!(requests[0].method, Method::Get);
+        assert_eq!(requests[0].url, "http://aoc/2021/day/6/input");
+        assert!(requests[0]
+            .headers
+            .contains(&("Cookie".to_string(), "session=s3cr3t".to_string())));
+    }
+
+    #[test]
+    fn test_no_session() {
+        let config = Config::default();
+        assert!(matches!(
+            Client::from_config(&config),
+            Err(ClientError::NoSession)
+        ));
+    }
+
+    #[test]
+    fn test_download_caches() {
+        let (url, received) = serve(vec![
+            (200, "3\n4\n".to_string()),
+            (404, "not yet".to_string()),
+        ]);
+        let client = Client::from_config(&config(&url)).unwrap();
+        let dir = std::env::temp_dir().join(format!("aoc-download-{}", std::process::id()));
+        let loader = InputLoader::new(&dir).with_user("alice");
+        let path = dir.join("alice").join("day_07.txt");
+
+        assert_eq!(
+            client.download(&loader, 7).unwrap(),
+            Download::Fetched(path.clone())
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "3\n4\n");
+        assert_eq!(client.download(&loader, 7).unwrap(), Download::Cached(path));
+        assert_eq!(received.lock().unwrap().len(), 1);
+        assert!(received.lock().unwrap()[0].starts_with("GET /2021/day/7/input HTTP/1.1\r\n"));
+
+        let error = client.download(&loader, 8).unwrap_err();
+        assert!(matches!(error, ClientError::Status { status: 404, .. }));
+        assert!(!dir.join("alice").join("day_08.txt").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
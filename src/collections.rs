@@ -0,0 +1,8 @@
+//! The maps and sets solvers use: hashed with `std`, ordered without it, where there is no
+//! source of randomness to seed a hasher and iteration order should be deterministic anyway.
+
+#[cfg(feature = "std")]
+pub use std::collections::{HashMap as Map, HashSet as Set};
+
+#[cfg(not(feature = "std"))]
+pub use alloc::collections::{BTreeMap as Map, BTreeSet as Set};
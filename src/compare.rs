@@ -0,0 +1,229 @@
+use std::path::Path;
+use std::time::Duration;
+
+use crate::bench::format_duration;
+use crate::input::{self, InputLoader};
+use crate::panic_hook::with_panic_hook;
+use crate::verify::{check_input, Check};
+use crate::DynSolution;
+
+/// How many times slower than the median input an input must be to be highlighted.
+pub const SLOW_FACTOR: f64 = 5.0;
+
+/// Both parts of a day run against one input.
+#[derive(Debug, Clone)]
+pub struct Row {
+    pub input: String,
+    pub checks: Vec<Check>,
+}
+
+impl Row {
+    /// Parse time plus the time spent solving every part.
+    pub fn total(&self) -> Duration {
+        let parse_time = self.checks.first().map_or(Duration::ZERO, |c| c.parse_time);
+        parse_time + self.checks.iter().map(|c| c.solve_time).sum::<Duration>()
+    }
+
+    pub fn panicked(&self) -> bool {
+        self.checks.iter().any(Check::panicked)
+    }
+}
+
+/// Every input the loader knows of for `day`, by name.
+pub fn discover(loader: &InputLoader, day: u8) -> Vec<(String, Result<String, String>)> {
+    loader
+        .discover(day)
+        .into_iter()
+        .map(|name| {
+            let input = loader.named(&name).load(day).map_err(|e| e.to_string());
+            (name, input)
+        })
+        .collect()
+}
+
+/// Every `<name>.txt` in `dir`, such as `input/day_04/alice.txt`.
+pub fn inputs_in(dir: &Path) -> Vec<(String, Result<String, String>)> {
+    input::inputs_in(dir)
+        .into_iter()
+        .map(|(name, path)| {
+            let input = std::fs::read_to_string(&path)
+                .map_err(|e| format!("failed to read `{}`: {}", path.display(), e));
+            (name, input)
+        })
+        .collect()
+}
+
+/// Runs both parts of `day` against every input. Panics are reported in the rows rather than
+/// printed, on this thread only.
+pub fn compare(day: &dyn DynSolution, inputs: Vec<(String, Result<String, String>)>) -> Vec<Row> {
+    with_panic_hook(|| {
+        inputs
+            .into_iter()
+            .map(|(name, input)| Row {
+                checks: check_input(day, &name, input, [None, None]),
+                input: name,
+            })
+            .collect()
+    })
+}
+
+/// How many times slower than the median row `row` is, if it is slower than `factor` times.
+///
+/// The lower median is used, so that of two rows the slower is measured against the faster.
+pub fn slowdown(row: &Row, rows: &[Row], factor: f64) -> Option<f64> {
+    let mut totals = rows.iter().map(Row::total).collect::<Vec<_>>();
+    totals.sort();
+    let median = totals.get(totals.len().checked_sub(1)? / 2)?.as_secs_f64();
+    let ratio = row.total().as_secs_f64() / median;
+    (median > 0.0 && ratio > factor).then_some(ratio)
+}
+
+/// One line per input with its answers and timings; a `!` marks panics and slow inputs.
+pub fn render_table(rows: &[Row], factor: f64) -> String {
+    let mut table = format!(
+        "{:<12} {:<16} {:<16} {:>11} {:>11}  {}\n",
+        "input", "part 1", "part 2", "parse", "solve", "notes"
+    );
+    for row in rows {
+        let answers = row
+            .checks
+            .iter()
+            .map(|check| match &check.actual {
+                Ok(answer) => answer.to_string(),
+                Err(_) if check.panicked() => "panicked".to_string(),
+                Err(_) => "error".to_string(),
+            })
+            .collect::<Vec<_>>();
+        let parse_time = row.checks.first().map_or(Duration::ZERO, |c| c.parse_time);
+        let solve_time = row.total() - parse_time;
+
+        let mut notes = Vec::new();
+        if let Some(error) = row
+            .checks
+            .iter()
+            .find_map(|check| check.actual.as_ref().err())
+        {
+            let first_line = error.lines().next().unwrap_or_default();
+            notes.push(if row.panicked() {
+                format!("! {}", first_line)
+            } else {
+                first_line.to_string()
+            });
+        }
+        if let Some(ratio) = slowdown(row, rows, factor) {
+            notes.push(format!("! {:.1}x slower than the median", ratio));
+        }
+
+        let line = format!(
+            "{:<12} {:<16} {:<16} {:>11} {:>11}  {}",
+            row.input,
+            answers.first().map_or("", String::as_str),
+            answers.get(1).map_or("", String::as_str),
+            format_duration(parse_time),
+            format_duration(solve_time),
+            notes.join(", ")
+        );
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Result, Solution};
+    use std::cell::Cell;
+    use std::panic;
+
+    struct Fragile;
+
+    impl Solution for Fragile {
+        const DAY: u8 = 1;
+        const TITLE: &'static str = "Fragile";
+
+        type Input = String;
+        type Answer = usize;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            Ok(input.to_string())
+        }
+
+        fn part1(input: &Self::Input) -> usize {
+            assert_ne!(input, "boom", "cannot solve boom");
+            input.len()
+        }
+
+        fn part2(input: &Self::Input) -> usize {
+            if input == "slow" {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            input.len() * 2
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let inputs = ["alice", "boom", "carol", "slow", "dave"]
+            .iter()
+            .map(|name| (name.to_string(), Ok(name.to_string())))
+            .collect();
+        let rows = compare(&Fragile, inputs);
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0].checks[0].actual, Ok(5usize.into()));
+        assert!(rows[1].panicked());
+        assert_eq!(rows[1].checks[1].actual, Ok(8usize.into()));
+        assert!(slowdown(&rows[3], &rows, SLOW_FACTOR).is_some());
+        assert!(slowdown(&rows[0], &rows, SLOW_FACTOR).is_none());
+
+        let table = render_table(&rows, SLOW_FACTOR);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert!(lines[2].contains("panicked"));
+        assert!(lines[2].contains("! panicked: "));
+        assert!(lines[2].contains("cannot solve boom"));
+        assert!(lines[4].contains("slower than the median"));
+        assert!(!lines[1].contains('!'));
+    }
+
+    #[test]
+    fn test_compare_keeps_hook() {
+        thread_local! {
+            static CALLED: Cell<bool> = const { Cell::new(false) };
+        }
+        // wraps whatever hook is installed, so other tests' panics are handled as before
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            CALLED.with(|called| called.set(true));
+            previous(info);
+        }));
+
+        let rows = compare(&Fragile, vec![("boom".to_string(), Ok("boom".to_string()))]);
+        assert!(rows[0].panicked());
+
+        CALLED.with(|called| called.set(false));
+        let _ = panic::catch_unwind(|| panic!("after compare"));
+        assert!(CALLED.with(Cell::get), "the caller's hook was replaced");
+    }
+
+    #[test]
+    fn test_slowdown_of_two() {
+        let inputs = ["alice", "slow"]
+            .iter()
+            .map(|name| (name.to_string(), Ok(name.to_string())))
+            .collect();
+        let rows = compare(&Fragile, inputs);
+        assert!(slowdown(&rows[1], &rows, SLOW_FACTOR).is_some());
+        assert!(slowdown(&rows[0], &rows, SLOW_FACTOR).is_none());
+        assert!(slowdown(&rows[0], &[], SLOW_FACTOR).is_none());
+    }
+
+    #[test]
+    fn test_inputs_in() {
+        assert!(inputs_in(Path::new("/nonexistent")).is_empty());
+
+        let inputs = inputs_in(&crate::examples::day_dir(4));
+        assert_eq!(inputs.len(), 1);
+        assert_eq!(inputs[0].0, "sample");
+        assert!(inputs[0].1.as_ref().unwrap().starts_with("7,4,9"));
+    }
+}
@@ -0,0 +1,152 @@
+use std::env;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::manifest::unquote;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_YEAR: u16 = 2021;
+
+/// Overrides the config file's location.
+pub const CONFIG_VAR: &str = "AOC_CONFIG";
+/// Overrides the configured session token.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Overrides the configured base url, e.g. to point at a local stand-in server.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Syntax { line: usize, message: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => write!(f, "failed to read `{}`: {}", path.display(), e),
+            ConfigError::Syntax { line, message } => write!(f, "config line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Settings for talking to the puzzle site, read from `.aoc.toml`:
+///
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// base_url = "https://adventofcode.com"
+/// year = 2021
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    pub year: u16,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            session: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            year: DEFAULT_YEAR,
+        }
+    }
+}
+
+impl Config {
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(".aoc.toml")
+    }
+
+    /// The config file (if any), then `AOC_SESSION` and `AOC_BASE_URL` on top.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let path = env::var_os(CONFIG_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(Self::default_path);
+        let mut config = Self::load(&path)?;
+        if let Ok(session) = env::var(SESSION_VAR) {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var(BASE_URL_VAR) {
+            config.base_url = base_url;
+        }
+        Ok(config)
+    }
+
+    /// Reads a config file, treating a missing one as empty.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => text.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(ConfigError::Io(path.to_path_buf(), e)),
+        }
+    }
+}
+
+impl std::str::FromStr for Config {
+    type Err = ConfigError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut config = Config::default();
+        for (idx, line) in text.lines().enumerate() {
+            let syntax = |message: String| ConfigError::Syntax {
+                line: idx + 1,
+                message,
+            };
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| syntax(format!("expected `key = value`, found `{}`", line)))?;
+            let value = value.trim();
+            let string = || {
+                unquote(value)
+                    .filter(|_| value.starts_with('"'))
+                    .ok_or_else(|| syntax(format!("expected a string, found `{}`", value)))
+            };
+            match key.trim() {
+                "session" => config.session = Some(string()?),
+                "base_url" => config.base_url = string()?,
+                "year" => {
+                    config.year = value
+                        .parse()
+                        .map_err(|_| syntax(format!("invalid year `{}`", value)))?
+                }
+                key => return Err(syntax(format!("unknown key `{}`", key))),
+            }
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config =
+            "# secrets\nsession = \"abc123\"\nbase_url = \"http://127.0.0.1:8080\"\nyear = 2022\n"
+                .parse::<Config>()
+                .unwrap();
+        assert_eq!(
+            config,
+            Config {
+                session: Some("abc123".to_string()),
+                base_url: "http://127.0.0.1:8080".to_string(),
+                year: 2022,
+            }
+        );
+
+        assert_eq!("".parse::<Config>().unwrap(), Config::default());
+        assert!("session = abc".parse::<Config>().is_err());
+        assert!("token = \"abc\"".parse::<Config>().is_err());
+        assert!("year = twenty".parse::<Config>().is_err());
+    }
+}
@@ -1,87 +1,170 @@
-pub fn solve_part1(input: &str) -> u32 {
-    let mut prev = None;
-    let mut increased = 0;
-    for line in input.lines() {
-        let num = line.trim().parse::<u32>().unwrap();
-        if let Some(prev) = prev {
-            if num > prev {
-                increased += 1;
-            }
-        }
+#[cfg(feature = "std")]
+use std::io::BufRead;
 
-        prev = Some(num);
-    }
-    increased
-}
+#[cfg(feature = "std")]
+use crate::error::ReadError;
+#[cfg(feature = "std")]
+use crate::normalize::for_each_line;
+use crate::prelude::*;
+#[cfg(feature = "std")]
+use crate::repl::{Explore, ExploreError};
+use crate::{normalize, parse, Result, Solution};
 
-pub fn solve_part2(input: &str) -> u32 {
-    let mut prev = None;
-    let mut cur = [0; 3];
-    let mut increased = 0;
-    for (idx, line) in input.lines().enumerate() {
-        let num = line.trim().parse::<u32>().unwrap();
-        cur[idx % 3] = num;
+pub struct Day01;
 
-        if idx >= 2 {
-            let sum: u32 = cur.iter().sum();
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Sonar Sweep";
+
+    type Input = Vec<u32>;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| parse::number(Self::DAY, idx, line, line.trim()))
+            .collect()
+    }
+
+    fn part1(depths: &Self::Input) -> u32 {
+        let mut prev = None;
+        let mut increased = 0;
+        for &num in depths {
             if let Some(prev) = prev {
-                if sum > prev {
+                if num > prev {
                     increased += 1;
                 }
             }
 
-            prev = Some(sum);
+            prev = Some(num);
+        }
+        increased
+    }
+
+    fn part2(depths: &Self::Input) -> u32 {
+        let mut prev = None;
+        let mut cur = [0; 3];
+        let mut increased = 0;
+        for (idx, &num) in depths.iter().enumerate() {
+            cur[idx % 3] = num;
+
+            if idx >= 2 {
+                let sum = cur.iter().map(|&n| u64::from(n)).sum::<u64>();
+                if let Some(prev) = prev {
+                    if sum > prev {
+                        increased += 1;
+                    }
+                }
+
+                prev = Some(sum);
+            }
+        }
+        increased
+    }
+}
+
+#[cfg(feature = "std")]
+impl Explore for Day01 {
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[(
+        "window <SIZE>",
+        "count increases between sliding sums of SIZE depths (part 1 is 1, part 2 is 3)",
+    )];
+
+    fn explore(
+        depths: &Self::Input,
+        command: &str,
+        args: &[&str],
+    ) -> core::result::Result<String, ExploreError> {
+        match (command, args) {
+            ("window", [size]) => {
+                let size = size
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&size| size > 0)
+                    .ok_or_else(|| format!("invalid window size `{}`", size))?;
+                Ok(count_window_increases(depths, size).to_string())
+            }
+            _ => Err(ExploreError::Usage),
         }
     }
-    increased
 }
+
+#[cfg(feature = "std")]
+fn count_window_increases(depths: &[u32], size: usize) -> usize {
+    let sums = depths
+        .windows(size)
+        .map(|window| window.iter().map(|&n| u64::from(n)).sum::<u64>())
+        .collect::<Vec<_>>();
+    sums.windows(2).filter(|pair| pair[1] > pair[0]).count()
+}
+
+pub fn solve_part1(input: &str) -> Result<u32> {
+    Ok(Day01::part1(&normalize::parse::<Day01>(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<u32> {
+    Ok(Day01::part2(&normalize::parse::<Day01>(input)?))
+}
+
+/// `solve_part1` reading one line at a time.
+#[cfg(feature = "std")]
+pub fn solve_part1_reader(reader: impl BufRead) -> core::result::Result<u32, ReadError> {
+    count_increases::<1>(reader)
+}
+
+/// `solve_part2` reading one line at a time.
+#[cfg(feature = "std")]
+pub fn solve_part2_reader(reader: impl BufRead) -> core::result::Result<u32, ReadError> {
+    count_increases::<3>(reader)
+}
+
+/// Counts increases between sums of `N` consecutive depths, keeping only the last `N`.
+#[cfg(feature = "std")]
+fn count_increases<const N: usize>(reader: impl BufRead) -> core::result::Result<u32, ReadError> {
+    let mut window = [0u32; N];
+    let mut seen = 0;
+    let mut increased = 0;
+    for_each_line(reader, |idx, line| {
+        let num = parse::number(Day01::DAY, idx, line, line.trim())?;
+        // the sums of two windows differ only by the depth leaving and the one entering
+        if seen >= N && num > window[seen % N] {
+            increased += 1;
+        }
+        window[seen % N] = num;
+        seen += 1;
+        Ok(())
+    })?;
+    Ok(increased)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ParseErrorKind;
 
     #[test]
-    fn test_part1_sample() {
-        let input = r#"199
-        200
-        208
-        210
-        200
-        207
-        240
-        269
-        260
-        263"#;
-        let answer = solve_part1(input);
-        assert_eq!(answer, 7);
+    fn test_parse_error() {
+        let error = solve_part1("199\n  2o0\n208").unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::InvalidNumber);
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.text, "2o0");
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn test_part1() {
-        let input = include_str!("../input/day_01.txt");
-        let answer = solve_part1(input);
-        assert_eq!(answer, 1215);
-    }
+    fn test_reader() {
+        let sample = crate::examples::input(1, "sample");
+        assert_eq!(solve_part1_reader(sample.as_bytes()).unwrap(), 7);
+        assert_eq!(solve_part2_reader(sample.as_bytes()).unwrap(), 5);
 
-    #[test]
-    fn test_part2_sample() {
-        let input = r#"199
-        200
-        208
-        210
-        200
-        207
-        240
-        269
-        260
-        263"#;
-        let answer = solve_part2(input);
-        assert_eq!(answer, 5);
-    }
+        let input = sample.replace('\n', "\r\n");
+        assert_eq!(solve_part2_reader(input.as_bytes()).unwrap(), 5);
 
-    #[test]
-    fn test_part2() {
-        let input = include_str!("../input/day_01.txt");
-        let answer = solve_part2(input);
-        assert_eq!(answer, 1150);
+        let error = solve_part1_reader("199\n  2o0\n208".as_bytes()).unwrap_err();
+        let ReadError::Parse(error) = error else {
+            panic!("expected a parse error, got {:?}", error);
+        };
+        assert_eq!((error.line, error.column), (2, 3));
     }
 }
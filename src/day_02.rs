@@ -1,62 +1,187 @@
-use std::str::FromStr;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io::BufRead;
 
-#[derive(Debug)]
-enum Command {
+#[cfg(feature = "std")]
+use crate::error::ReadError;
+use crate::error::{ParseError, ParseErrorKind};
+#[cfg(feature = "std")]
+use crate::normalize::for_each_line;
+use crate::prelude::*;
+#[cfg(feature = "std")]
+use crate::repl::{Explore, ExploreError};
+use crate::{normalize, parse, Result, Solution};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Command {
     Forward,
     Up,
     Down,
 }
 
 impl FromStr for Command {
-    type Err = ();
+    type Err = ParseErrorKind;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
         match s {
             "forward" => Ok(Command::Forward),
             "up" => Ok(Command::Up),
             "down" => Ok(Command::Down),
-            _ => Err(()),
+            _ => Err(ParseErrorKind::Unexpected {
+                expected: "`forward`, `up` or `down`".to_string(),
+            }),
         }
     }
 }
 
-pub fn solve_part1(input: &str) -> u32 {
-    let mut x = 0;
-    let mut depth = 0;
-    for line in input.lines() {
-        let mut parts = line.split_whitespace();
-        let command = parts.next().unwrap().parse::<Command>().unwrap();
-        let value = parts.next().unwrap().parse::<u32>().unwrap();
-
-        match command {
-            Command::Forward => x += value,
-            Command::Up => depth -= value,
-            Command::Down => depth += value,
-        }
+pub struct Day02;
+
+/// Commands in order, only made by parsing, which checks following them never overflows.
+#[derive(Debug, Clone)]
+pub struct Course(Vec<(Command, u32)>);
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Dive!";
+
+    type Input = Course;
+    type Answer = i64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut position = (0, 0, 0);
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| parse_step(&mut position, idx, line))
+            .collect::<Result<_>>()
+            .map(Course)
+    }
+
+    fn part1(course: &Self::Input) -> i64 {
+        let (horizon, _, aim) = position(&course.0);
+        horizon * aim
     }
 
-    x * depth
+    fn part2(course: &Self::Input) -> i64 {
+        let (horizon, depth, _) = position(&course.0);
+        horizon * depth
+    }
 }
 
-pub fn solve_part2(input: &str) -> u32 {
-    let mut aim = 0;
-    let (mut horizon, mut depth) = (0, 0);
-    for line in input.lines() {
-        let mut parts = line.split_whitespace();
-        let command = parts.next().unwrap().parse::<Command>().unwrap();
-        let value = parts.next().unwrap().parse::<u32>().unwrap();
-
-        match command {
-            Command::Forward => {
-                horizon += value;
-                depth += aim * value;
+#[cfg(feature = "std")]
+impl Explore for Day02 {
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[(
+        "step <N>",
+        "position after the first N commands, with and without aim",
+    )];
+
+    fn explore(
+        course: &Self::Input,
+        command: &str,
+        args: &[&str],
+    ) -> core::result::Result<String, ExploreError> {
+        match (command, args) {
+            ("step", [n]) => {
+                let n = n
+                    .parse::<usize>()
+                    .map_err(|_| format!("invalid step count `{}`", n))?;
+                let commands = &course.0[..n.min(course.0.len())];
+                let (horizon, depth, aim) = position(commands);
+                Ok(format!(
+                    "after {} commands\n  part 1: horizontal {}, depth {}\n  part 2: horizontal {}, depth {}, aim {}",
+                    commands.len(),
+                    horizon,
+                    aim,
+                    horizon,
+                    depth,
+                    aim
+                ))
             }
-            Command::Up => aim -= value,
-            Command::Down => aim += value,
+            _ => Err(ExploreError::Usage),
         }
     }
+}
+
+/// `(horizontal, depth, aim)` when following commands with aim. Without aim the depth is
+/// simply the aim.
+type Position = (i64, i64, i64);
+
+/// The position after `commands`, which must be (a prefix of) a parsed `Course`.
+fn position(commands: &[(Command, u32)]) -> Position {
+    commands
+        .iter()
+        .try_fold((0, 0, 0), |position, &command| step(position, command))
+        .expect("parsing checks a course never overflows")
+}
+
+/// Follows one command, or returns `None` if the position or either answer would overflow.
+fn step((horizon, depth, aim): Position, (command, value): (Command, u32)) -> Option<Position> {
+    let value = i64::from(value);
+    let position = match command {
+        Command::Forward => (
+            horizon.checked_add(value)?,
+            depth.checked_add(aim.checked_mul(value)?)?,
+            aim,
+        ),
+        Command::Up => (horizon, depth, aim.checked_sub(value)?),
+        Command::Down => (horizon, depth, aim.checked_add(value)?),
+    };
+    position.0.checked_mul(position.1)?;
+    position.0.checked_mul(position.2)?;
+    Some(position)
+}
 
-    horizon * depth
+/// `solve_part1` reading one line at a time.
+#[cfg(feature = "std")]
+pub fn solve_part1_reader(reader: impl BufRead) -> core::result::Result<i64, ReadError> {
+    let (horizon, _, aim) = follow(reader)?;
+    Ok(horizon * aim)
+}
+
+/// `solve_part2` reading one line at a time.
+#[cfg(feature = "std")]
+pub fn solve_part2_reader(reader: impl BufRead) -> core::result::Result<i64, ReadError> {
+    let (horizon, depth, _) = follow(reader)?;
+    Ok(horizon * depth)
+}
+
+#[cfg(feature = "std")]
+fn follow(reader: impl BufRead) -> core::result::Result<Position, ReadError> {
+    let mut position = (0, 0, 0);
+    for_each_line(reader, |idx, line| {
+        parse_step(&mut position, idx, line)?;
+        Ok(())
+    })?;
+    Ok(position)
+}
+
+/// Parses a line and follows it from `position`, failing if that would overflow.
+///
+/// Overflow is caught here because an input whose answer does not fit an `i64` has no answer
+/// to give, and `part1` and `part2` cannot report errors. Fuzzing found such inputs panicking.
+fn parse_step(position: &mut Position, idx: usize, line: &str) -> Result<(Command, u32)> {
+    let command = parse_line(idx, line)?;
+    *position = step(*position, command).ok_or_else(|| {
+        ParseError::at(Day02::DAY, ParseErrorKind::Overflow, idx, line, line.trim())
+    })?;
+    Ok(command)
+}
+
+fn parse_line(idx: usize, line: &str) -> Result<(Command, u32)> {
+    let [command, value] = parse::fields(Day02::DAY, idx, line, ["command", "number"])?;
+    let command = command
+        .parse::<Command>()
+        .map_err(|kind| ParseError::at(Day02::DAY, kind, idx, line, command))?;
+    let value = parse::number(Day02::DAY, idx, line, value)?;
+    Ok((command, value))
+}
+
+pub fn solve_part1(input: &str) -> Result<i64> {
+    Ok(Day02::part1(&normalize::parse::<Day02>(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<i64> {
+    Ok(Day02::part2(&normalize::parse::<Day02>(input)?))
 }
 
 #[cfg(test)]
@@ -64,40 +189,46 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part1_sample() {
-        let input = r#"forward 5
-        down 5
-        forward 8
-        up 3
-        down 8
-        forward 2"#;
-        let answer = solve_part1(input);
-        assert_eq!(answer, 150);
-    }
+    fn test_parse_error() {
+        let error = solve_part1("forward 5\nbackward 3").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.text, "backward");
 
-    #[test]
-    fn test_part1() {
-        let input = include_str!("../input/day_02.txt");
-        let answer = solve_part1(input);
-        assert_eq!(answer, 1698735);
+        let error = solve_part1("forward 5\nup").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.to_string(), "missing number");
+
+        let error = solve_part1("down 5 5").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 8));
+
+        let error = solve_part2("down 4294967295\nforward 4294967295").unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::Overflow);
+        assert_eq!(error.line, 2);
     }
 
     #[test]
-    fn test_part2_sample() {
-        let input = r#"forward 5
-        down 5
-        forward 8
-        up 3
-        down 8
-        forward 2"#;
-        let answer = solve_part2(input);
-        assert_eq!(answer, 900);
+    fn test_overflow() {
+        // just within an i64, as horizontal position times aim
+        let input = "forward 2147483647\ndown 4294967295";
+        assert_eq!(solve_part1(input), Ok(2147483647 * 4294967295));
+        assert_eq!(solve_part2(input), Ok(0));
+
+        // part 1 alone would overflow, which rejects the input for both parts
+        let input = "forward 4294967295\ndown 4294967295";
+        let error = solve_part1(input).unwrap_err();
+        assert_eq!((&error.kind, error.line), (&ParseErrorKind::Overflow, 2));
+        assert_eq!(solve_part2(input), Err(error));
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn test_part2() {
-        let input = include_str!("../input/day_02.txt");
-        let answer = solve_part2(input);
-        assert_eq!(answer, 1594785890);
+    fn test_reader() {
+        let sample = crate::examples::input(2, "sample");
+        assert_eq!(solve_part1_reader(sample.as_bytes()).unwrap(), 150);
+        assert_eq!(solve_part2_reader(sample.as_bytes()).unwrap(), 900);
+        assert!(matches!(
+            solve_part1_reader("forward 5\nup".as_bytes()),
+            Err(ReadError::Parse(error)) if error.line == 2
+        ));
     }
 }
@@ -1,64 +1,194 @@
-pub fn solve_part1(input: &str) -> u32 {
-    let count = bit_count(input);
-    let input_bits = input_to_bits(input);
-    let gamma_rate = most_common_bits(count, &input_bits);
-    let epsilon_rate = least_common_bits(count, &input_bits);
+#[cfg(feature = "std")]
+use std::io::BufRead;
 
-    gamma_rate * epsilon_rate
+#[cfg(feature = "std")]
+use crate::error::ReadError;
+use crate::error::{ParseError, ParseErrorKind};
+#[cfg(feature = "std")]
+use crate::normalize::for_each_line;
+use crate::prelude::*;
+#[cfg(feature = "std")]
+use crate::repl::{Explore, ExploreError};
+use crate::{normalize, parse, trace, Result, Solution};
+
+pub struct Day03;
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    count: u32,
+    bits: Vec<u32>,
 }
 
-pub fn solve_part2(input: &str) -> u32 {
-    let count = bit_count(input);
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Binary Diagnostic";
 
-    // oxygen
-    let mut remained = input_to_bits(input);
-    let mut oxygen = 0;
-    for bit_idx in (0..count).rev() {
-        let most_bits = most_common_bits(count, &remained);
-        remained.retain(|&bit| (bit & 1 << bit_idx) == (most_bits & 1 << bit_idx));
-        if remained.len() == 1 {
-            oxygen = remained[0];
-            break;
+    type Input = Report;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut count = None;
+        let mut bits = Vec::new();
+        for (idx, line) in input.lines().enumerate() {
+            bits.push(parse_line(&mut count, idx, line)?);
         }
+
+        let count = count.ok_or_else(|| ParseError::empty_input(Self::DAY))?;
+        Ok(Report {
+            count: count as u32,
+            bits,
+        })
+    }
+
+    fn part1(report: &Self::Input) -> u64 {
+        let count = report.count;
+        let gamma_rate = most_common_bits(count, &report.bits);
+        let epsilon_rate = least_common_bits(count, &report.bits);
+        trace!("rates", gamma = gamma_rate, epsilon = epsilon_rate);
+
+        u64::from(gamma_rate) * u64::from(epsilon_rate)
     }
 
-    // co2
-    let mut remained = input_to_bits(input);
-    let mut co2 = 0;
-    for idx in (0..count).rev() {
-        let least_bits = least_common_bits(count, &remained);
-        remained.retain(|&bit| bit & 1 << idx == least_bits & 1 << idx);
+    fn part2(report: &Self::Input) -> u64 {
+        let oxygen = rating(report, most_common_bits, |bit, remained| {
+            trace!("oxygen filter", bit = bit, kept = remained.len());
+        });
+        let co2 = rating(report, least_common_bits, |bit, remained| {
+            trace!("co2 filter", bit = bit, kept = remained.len());
+        });
+        trace!("ratings", oxygen = oxygen, co2 = co2);
+
+        u64::from(oxygen) * u64::from(co2)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Explore for Day03 {
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[
+        ("gamma", "gamma rate, the most common bit in each position"),
+        (
+            "epsilon",
+            "epsilon rate, the least common bit in each position",
+        ),
+        (
+            "rating <oxygen|co2>",
+            "step through the bit criteria of a rating",
+        ),
+    ];
+
+    fn explore(
+        report: &Self::Input,
+        command: &str,
+        args: &[&str],
+    ) -> core::result::Result<String, ExploreError> {
+        let count = report.count as usize;
+        match (command, args) {
+            ("gamma", []) => {
+                let gamma = most_common_bits(report.count, &report.bits);
+                Ok(format!("{:0count$b} ({})", gamma, gamma, count = count))
+            }
+            ("epsilon", []) => {
+                let epsilon = least_common_bits(report.count, &report.bits);
+                Ok(format!("{:0count$b} ({})", epsilon, epsilon, count = count))
+            }
+            ("rating", [kind]) => {
+                let select = match *kind {
+                    "oxygen" => most_common_bits,
+                    "co2" => least_common_bits,
+                    _ => return Err(format!("unknown rating `{}`", kind).into()),
+                };
+                let mut out = Vec::new();
+                let rating = rating(report, select, |bit_idx, remained| {
+                    out.push(format!("bit {:2}: {} remaining", bit_idx, remained.len()));
+                });
+                out.push(format!("{:0count$b} ({})", rating, rating, count = count));
+                Ok(out.join("\n"))
+            }
+            _ => Err(ExploreError::Usage),
+        }
+    }
+}
+
+/// Filters the report by the bit criteria chosen by `select`, most significant bit first,
+/// calling `step` with the values that remain after each bit.
+fn rating(
+    report: &Report,
+    select: fn(u32, &[u32]) -> u32,
+    mut step: impl FnMut(u32, &[u32]),
+) -> u32 {
+    let count = report.count;
+    let mut remained = report.bits.clone();
+    for bit_idx in (0..count).rev() {
+        // a single value is the rating, even one the criteria would filter out
         if remained.len() == 1 {
-            co2 = remained[0];
             break;
         }
+        let bits = select(count, &remained);
+        remained.retain(|&bit| (bit & 1 << bit_idx) == (bits & 1 << bit_idx));
+        step(bit_idx, &remained);
     }
 
-    println!("oxygen: {:b}", oxygen);
-    println!("co2: {:b}", co2);
+    match remained[..] {
+        [rating] => rating,
+        _ => 0,
+    }
+}
 
-    oxygen * co2
+pub fn solve_part1(input: &str) -> Result<u64> {
+    Ok(Day03::part1(&normalize::parse::<Day03>(input)?))
 }
 
-fn input_to_bits(input: &str) -> Vec<u32> {
-    input.lines().map(|line| to_bits(line.trim())).collect()
+pub fn solve_part2(input: &str) -> Result<u64> {
+    Ok(Day03::part2(&normalize::parse::<Day03>(input)?))
 }
 
-fn bit_count(input: &str) -> u32 {
-    let first = input.lines().next().unwrap().trim();
-    first.len() as u32
+/// `solve_part1` reading one line at a time, counting the bits in each position as it goes.
+#[cfg(feature = "std")]
+pub fn solve_part1_reader(reader: impl BufRead) -> core::result::Result<u64, ReadError> {
+    let mut count = None;
+    // ones minus zeros seen in each position, least significant first
+    let mut balance = [0i64; 32];
+    for_each_line(reader, |idx, line| {
+        let bits = parse_line(&mut count, idx, line)?;
+        for (bit_idx, balance) in balance.iter_mut().enumerate() {
+            *balance += if bits & (1 << bit_idx) != 0 { 1 } else { -1 };
+        }
+        Ok(())
+    })?;
+
+    let count = count.ok_or_else(|| ParseError::empty_input(Day03::DAY))?;
+    let (mut gamma_rate, mut epsilon_rate) = (0u32, 0u32);
+    for (bit_idx, &balance) in balance.iter().enumerate().take(count) {
+        if balance >= 0 {
+            gamma_rate |= 1 << bit_idx;
+        } else {
+            epsilon_rate |= 1 << bit_idx;
+        }
+    }
+    Ok(u64::from(gamma_rate) * u64::from(epsilon_rate))
 }
 
-fn to_bits(input: &str) -> u32 {
-    let mut result = 0;
-    for c in input.chars() {
-        result <<= 1;
-        result |= u32::from(c == '1');
+/// Parses one line of bits, checking it is as long as the first line, whose length is
+/// recorded in `count`.
+fn parse_line(count: &mut Option<usize>, idx: usize, line: &str) -> Result<u32> {
+    let bit = |c| match c {
+        '0' => Some(false),
+        '1' => Some(true),
+        _ => None,
+    };
+    let bits = parse::char_row(Day03::DAY, idx, line, count, bit, "`0` or `1`")?;
+    if bits.len() > 32 {
+        let kind = ParseErrorKind::Unexpected {
+            expected: "at most 32 bits".to_string(),
+        };
+        return Err(ParseError::at(Day03::DAY, kind, idx, line, line.trim()));
     }
-    result
+    Ok(bits
+        .iter()
+        .fold(0, |result, &bit| result << 1 | u32::from(bit)))
 }
 
-fn most_common_bits(len: u32, bits: &Vec<u32>) -> u32 {
+fn most_common_bits(len: u32, bits: &[u32]) -> u32 {
     let mut result = 0;
     for bit_idx in 0..len {
         let mut count_1 = 0;
@@ -78,7 +208,7 @@ fn most_common_bits(len: u32, bits: &Vec<u32>) -> u32 {
     result
 }
 
-fn least_common_bits(len: u32, bits: &Vec<u32>) -> u32 {
+fn least_common_bits(len: u32, bits: &[u32]) -> u32 {
     let mut result = 0;
     for bit_idx in 0..len {
         let mut count_1 = 0;
@@ -103,52 +233,61 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part1_sample() {
-        let input = r#"00100
-        11110
-        10110
-        10111
-        10101
-        01111
-        00111
-        11100
-        10000
-        11001
-        00010
-        01010"#;
-        let answer = solve_part1(input);
-        assert_eq!(answer, 198);
+    fn test_parse_error() {
+        let error = solve_part1("00100\n11210").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.text, "2");
+
+        let error = solve_part1("00100\n1111").unwrap_err();
+        assert_eq!(error.to_string(), "expected 5 characters, found `1111`");
+
+        let error = solve_part1("").unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::EmptyInput);
     }
 
     #[test]
-    fn test_part1() {
-        let input = include_str!("../input/day_03.txt");
-        let answer = solve_part1(input);
-        assert_eq!(answer, 852500);
+    fn test_single_number() {
+        // both ratings are the only number, though it is not the least common bit's
+        assert_eq!(solve_part2("10110"), Ok(22 * 22));
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn test_part2_sample() {
-        let input = r#"00100
-        11110
-        10110
-        10111
-        10101
-        01111
-        00111
-        11100
-        10000
-        11001
-        00010
-        01010"#;
-        let answer = solve_part2(input);
-        assert_eq!(answer, 230);
+    fn test_reader() {
+        let sample = crate::examples::input(3, "sample");
+        assert_eq!(solve_part1_reader(sample.as_bytes()).unwrap(), 198);
+        assert!(matches!(
+            solve_part1_reader("00100\n1111".as_bytes()),
+            Err(ReadError::Parse(error)) if error.line == 2
+        ));
+        assert!(matches!(
+            solve_part1_reader("".as_bytes()),
+            Err(ReadError::Parse(error)) if error.kind == ParseErrorKind::EmptyInput
+        ));
+
+        let wide = "1".repeat(32) + "\n" + &"0".repeat(32);
+        assert_eq!(
+            solve_part1_reader(wide.as_bytes()).unwrap(),
+            solve_part1(&wide).unwrap()
+        );
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn test_part2() {
-        let input = include_str!("../input/day_03.txt");
-        let answer = solve_part2(input);
-        assert_eq!(answer, 1007985);
+    fn test_trace() {
+        let report = Day03::parse(&crate::examples::input(3, "sample")).unwrap();
+        let (answer, events) = crate::trace::collect(|| Day03::part2(&report));
+        assert_eq!(answer, 230);
+
+        let kept = events
+            .iter()
+            .filter(|event| event.name == "oxygen filter")
+            .map(|event| event.field("kept").unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(kept, ["7", "4", "3", "2", "1"]);
+        assert_eq!(
+            events.last().unwrap().to_string(),
+            "ratings oxygen=23 co2=10"
+        );
     }
 }
@@ -1,45 +1,173 @@
-use std::collections::HashSet;
+use crate::collections::Set;
+use crate::error::{ParseError, ParseErrorKind};
+use crate::prelude::*;
+#[cfg(feature = "std")]
+use crate::repl::{Explore, ExploreError};
+use crate::{normalize, parse, trace, Result, Solution};
 
-pub fn solve_part1(input: &str) -> u32 {
-    let (numbers, mut grids) = parse_input(input);
+pub struct Day04;
 
-    for n in numbers {
-        for grid in grids.iter_mut() {
-            mark(n, grid);
-            if is_winner(grid) {
-                let sum: u32 = sum_unmarked(grid);
-                return sum * n;
+type Grid = [[Number; 5]; 5];
+
+#[derive(Debug, Clone)]
+pub struct Bingo {
+    numbers: Vec<u32>,
+    grids: Vec<Grid>,
+}
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Giant Squid";
+
+    type Input = Bingo;
+    /// A score is up to 25 unmarked `u32`s times a drawn `u32`, which can overflow `u64`.
+    type Answer = u128;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let (numbers, grids) = parse_input(input)?;
+        Ok(Bingo { numbers, grids })
+    }
+
+    fn part1(bingo: &Self::Input) -> u128 {
+        let mut grids = bingo.grids.clone();
+
+        for &n in &bingo.numbers {
+            for (idx, grid) in grids.iter_mut().enumerate() {
+                mark(n, grid);
+                if is_winner(grid) {
+                    let sum = sum_unmarked(grid);
+                    trace!("board won", board = idx, draw = n, unmarked = sum);
+                    return sum * u128::from(n);
+                }
             }
         }
+
+        0
     }
 
-    0
-}
+    fn part2(bingo: &Self::Input) -> u128 {
+        let mut grids = bingo.grids.clone();
 
-pub fn solve_part2(input: &str) -> u32 {
-    let (numbers, mut grids) = parse_input(input);
+        let grids_count = grids.len();
 
-    let grids_count = grids.len();
+        let mut winned = Set::new();
+        for &n in &bingo.numbers {
+            for (idx, grid) in grids.iter_mut().enumerate() {
+                if winned.contains(&idx) {
+                    continue;
+                }
 
-    let mut winned = HashSet::new();
-    for n in numbers {
-        for (idx, grid) in grids.iter_mut().enumerate() {
-            if winned.contains(&idx) {
-                continue;
+                mark(n, grid);
+                if is_winner(grid) {
+                    winned.insert(idx);
+                    trace!(
+                        "board won",
+                        board = idx,
+                        draw = n,
+                        left = grids_count - winned.len()
+                    );
+                    if winned.len() == grids_count {
+                        let sum = sum_unmarked(grid);
+                        return sum * u128::from(n);
+                    }
+                }
             }
+        }
+
+        0
+    }
+}
+
+#[cfg(feature = "std")]
+impl Explore for Day04 {
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[
+        (
+            "board <I> [DRAWN]",
+            "show board I after DRAWN numbers, marked ones in brackets",
+        ),
+        (
+            "play <DRAWN>",
+            "draw the first DRAWN numbers and list the winning boards",
+        ),
+    ];
+
+    fn explore(
+        bingo: &Self::Input,
+        command: &str,
+        args: &[&str],
+    ) -> core::result::Result<String, ExploreError> {
+        let count = |arg: &str, what: &str, max: usize| {
+            arg.parse::<usize>()
+                .ok()
+                .filter(|&n| n <= max)
+                .ok_or_else(|| format!("{} must be at most {}, found `{}`", what, max, arg))
+        };
 
-            mark(n, grid);
-            if is_winner(grid) {
-                winned.insert(idx);
-                if winned.len() == grids_count {
-                    let sum: u32 = sum_unmarked(grid);
-                    return sum * n;
+        match (command, args) {
+            ("board", [index, rest @ ..]) if rest.len() <= 1 => {
+                let last = bingo.grids.len().saturating_sub(1);
+                let index = count(index, "board", last)?;
+                let drawn = match rest {
+                    [drawn] => count(drawn, "drawn", bingo.numbers.len())?,
+                    _ => 0,
+                };
+                let mut grid = *bingo.grids.get(index).ok_or("there are no boards")?;
+                for &n in &bingo.numbers[..drawn] {
+                    mark(n, &mut grid);
                 }
+                Ok(render(&grid))
             }
+            ("play", [drawn]) => {
+                let drawn = count(drawn, "drawn", bingo.numbers.len())?;
+                let mut grids = bingo.grids.clone();
+                let mut out = Vec::new();
+                let mut winned = Set::new();
+                for (round, &n) in bingo.numbers[..drawn].iter().enumerate() {
+                    for (idx, grid) in grids.iter_mut().enumerate() {
+                        mark(n, grid);
+                        if is_winner(grid) && winned.insert(idx) {
+                            let score = sum_unmarked(grid) * u128::from(n);
+                            out.push(format!(
+                                "round {}: board {} wins on {}, score {}",
+                                round + 1,
+                                idx,
+                                n,
+                                score
+                            ));
+                        }
+                    }
+                }
+                out.push(format!("{} of {} boards won", winned.len(), grids.len()));
+                Ok(out.join("\n"))
+            }
+            _ => Err(ExploreError::Usage),
         }
     }
+}
 
-    0
+#[cfg(feature = "std")]
+fn render(grid: &Grid) -> String {
+    grid.iter()
+        .map(|row| {
+            row.iter()
+                .map(|number| match number {
+                    Number::Marked(n) => format!("[{:2}]", n),
+                    Number::Unmarked(n) => format!(" {:2} ", n),
+                })
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn solve_part1(input: &str) -> Result<u128> {
+    Ok(Day04::part1(&normalize::parse::<Day04>(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<u128> {
+    Ok(Day04::part2(&normalize::parse::<Day04>(input)?))
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -64,66 +192,61 @@ impl Number {
     }
 }
 
-fn sum_unmarked(grid: &[[Number; 5]; 5]) -> u32 {
+fn sum_unmarked(grid: &Grid) -> u128 {
     grid.iter()
         .flatten()
         .filter(|m| !m.is_marked())
-        .map(|m| m.value())
+        .map(|m| u128::from(m.value()))
         .sum()
 }
 
-fn parse_input(input: &str) -> (Vec<u32>, Vec<[[Number; 5]; 5]>) {
-    let mut lines = input.lines();
-    let numbers = lines
-        .next()
-        .unwrap()
-        .trim()
-        .split(',')
-        .map(|s| s.parse::<u32>().unwrap())
-        .collect::<Vec<u32>>();
-
-    let mut grids = Vec::new();
-
-    while let Some(_) = lines.next() {
-        let mut grid = [[Number::Unmarked(0); 5]; 5];
-
-        for y in 0..5 {
-            let line = lines.next().unwrap();
-            for (x, number) in line.trim().split_ascii_whitespace().enumerate() {
-                grid[y][x] = Number::Unmarked(number.parse::<u32>().unwrap());
-            }
-        }
-
-        grids.push(grid);
+fn parse_input(input: &str) -> Result<(Vec<u32>, Vec<Grid>)> {
+    let blocks = parse::blocks(input);
+    let (first, boards) = blocks
+        .split_first()
+        .ok_or_else(|| ParseError::empty_input(Day04::DAY))?;
+    let mut lines = first.indexed();
+    let (idx, line) = lines.next().expect("blocks are never empty");
+    let numbers = parse::list(Day04::DAY, idx, line, ',')?;
+    if let Some((idx, line)) = lines.next() {
+        let kind = ParseErrorKind::Unexpected {
+            expected: "blank line".to_string(),
+        };
+        return Err(ParseError::at(Day04::DAY, kind, idx, line, line.trim()));
     }
 
-    (numbers, grids)
+    let grids = boards
+        .iter()
+        .map(|board| {
+            let grid = parse::grid::<u32, 5, 5>(Day04::DAY, board)?;
+            Ok(grid.map(|row| row.map(Number::Unmarked)))
+        })
+        .collect::<Result<_>>()?;
+    Ok((numbers, grids))
 }
 
-fn mark(num: u32, grid: &mut [[Number; 5]; 5]) {
-    for y in 0..5 {
-        for x in 0..5 {
-            if let Number::Unmarked(n) = grid[y][x] {
-                if n == num {
-                    grid[y][x] = Number::Marked(num);
-                    return;
-                }
+fn mark(num: u32, grid: &mut Grid) {
+    for number in grid.iter_mut().flatten() {
+        if let Number::Unmarked(n) = *number {
+            if n == num {
+                *number = Number::Marked(num);
+                return;
             }
         }
     }
 }
 
-fn is_winner(grid: &[[Number; 5]; 5]) -> bool {
+fn is_winner(grid: &Grid) -> bool {
     // horizontal
-    for y in 0..5 {
-        if (0..5).all(|x| grid[y][x].is_marked()) {
+    for row in grid {
+        if row.iter().all(|n| n.is_marked()) {
             return true;
         }
     }
 
     // vertical
     for x in 0..5 {
-        if (0..5).all(|y| grid[y][x].is_marked()) {
+        if grid.iter().all(|row| row[x].is_marked()) {
             return true;
         }
     }
@@ -136,66 +259,39 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part1_sample() {
-        let input = r#"7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
-
-        22 13 17 11  0
-         8  2 23  4 24
-        21  9 14 16  7
-         6 10  3 18  5
-         1 12 20 15 19
-        
-         3 15  0  2 22
-         9 18 13 17  5
-        19  8  7 25 23
-        20 11 10 24  4
-        14 21 16 12  6
-        
-        14 21 17 24  4
-        10 16 15  9 19
-        18  8 23 26 20
-        22 11 13  6  5
-         2  0 12  3  7"#;
-        let answer = solve_part1(input);
-        assert_eq!(answer, 4512);
-    }
+    fn test_parse_error() {
+        let error = solve_part1("7,4,x\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
 
-    #[test]
-    fn test_part1() {
-        let input = include_str!("../input/day_04.txt");
-        let answer = solve_part1(input);
-        assert_eq!(answer, 39984);
-    }
+        let error = solve_part1("7,4\n\n1 2 3 4 5\n1 2 3 4\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 8));
+        assert_eq!(error.to_string(), "missing number");
 
-    #[test]
-    fn test_part2_sample() {
-        let input = r#"7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
-
-        22 13 17 11  0
-         8  2 23  4 24
-        21  9 14 16  7
-         6 10  3 18  5
-         1 12 20 15 19
-        
-         3 15  0  2 22
-         9 18 13 17  5
-        19  8  7 25 23
-        20 11 10 24  4
-        14 21 16 12  6
-        
-        14 21 17 24  4
-        10 16 15  9 19
-        18  8 23 26 20
-        22 11 13  6  5
-         2  0 12  3  7"#;
-        let answer = solve_part2(input);
-        assert_eq!(answer, 1924);
+        let error = solve_part1("7,4\n\n1 2 3 4 5 6\n").unwrap_err();
+        assert_eq!(error.text, "6");
+
+        let error = solve_part1("7,4\n\n1 2 3 4 5\n1 2 3 4 5").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 10));
+        assert_eq!(error.to_string(), "missing row");
     }
 
     #[test]
-    fn test_part2() {
-        let input = include_str!("../input/day_04.txt");
-        let answer = solve_part2(input);
-        assert_eq!(answer, 8468);
+    fn test_large_score() {
+        let max = u32::MAX;
+        let mut input = format!("1,2,3,4,{}\n\n1 2 3 4 {}\n", max, max);
+        for _ in 0..4 {
+            input.push_str(&format!(
+                "{} {} {} {} {}\n",
+                max - 1,
+                max - 1,
+                max - 1,
+                max - 1,
+                max - 1
+            ));
+        }
+        let expected = 20 * u128::from(max - 1) * u128::from(max);
+        assert!(expected > u128::from(u64::MAX));
+        assert_eq!(solve_part1(&input), Ok(expected));
+        assert_eq!(solve_part2(&input), Ok(expected));
     }
 }
@@ -1,38 +1,172 @@
-use std::collections::HashMap;
+use crate::collections::Map;
+use crate::error::{ParseError, ParseErrorKind};
+use crate::prelude::*;
+#[cfg(feature = "std")]
+use crate::repl::{Explore, ExploreError};
+use crate::{normalize, parse, trace, Result, Solution};
 
-pub fn solve_part1(input: &str) -> i32 {
-    let lines = parse_input(input)
-        .iter()
-        .filter(|l| l.line_type().part1_type())
-        .copied()
-        .collect::<Vec<_>>();
+pub struct Day05;
 
-    let mut cross_counter: HashMap<(i32, i32), i32> = HashMap::new();
-    for i in 0..(lines.len() - 1) {
-        for j in (i + 1)..lines.len() {
-            let crosses = lines[i].cross(&lines[j]);
-            for cross in crosses {
-                let count = cross_counter.entry(cross).or_insert(0);
-                *count += 1;
+/// The largest coordinate accepted. Puzzle inputs stay below 1000; without a bound, fuzzing
+/// found coordinates near `i32::MAX` overflowing the products in `Line::has_point` and lines
+/// with billions of points. This one keeps those products within `i32` and lines short.
+const MAX_COORDINATE: i32 = 9999;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Hydrothermal Venture";
+
+    type Input = Vec<Line>;
+    type Answer = i32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(lines: &Self::Input) -> i32 {
+        let lines = lines
+            .iter()
+            .filter(|l| l.line_type().part1_type())
+            .copied()
+            .collect::<Vec<_>>();
+        trace!("lines considered", count = lines.len());
+
+        count_crosses(&lines)
+    }
+
+    fn part2(lines: &Self::Input) -> i32 {
+        let lines = lines
+            .iter()
+            .filter(|l| l.line_type().part2_type())
+            .copied()
+            .collect::<Vec<_>>();
+        trace!("lines considered", count = lines.len());
+
+        count_crosses(&lines)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Explore for Day05 {
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[
+        (
+            "lines [TYPE]",
+            "list the vent lines with their type, optionally only one type",
+        ),
+        ("types", "count the vent lines of each type"),
+        (
+            "crosses <TYPE>...",
+            "count overlapping points between lines of the given types",
+        ),
+        (
+            "diagram [TYPE]...",
+            "draw how many lines cover each point (small inputs only)",
+        ),
+    ];
+
+    fn explore(
+        lines: &Self::Input,
+        command: &str,
+        args: &[&str],
+    ) -> core::result::Result<String, ExploreError> {
+        let types = args
+            .iter()
+            .map(|arg| LineType::from_name(arg))
+            .collect::<core::result::Result<Vec<_>, _>>()?;
+        let selected = |types: &[LineType]| {
+            lines
+                .iter()
+                .filter(|l| types.contains(&l.line_type()))
+                .copied()
+                .collect::<Vec<_>>()
+        };
+
+        match (command, types.as_slice()) {
+            ("lines", [] | [_]) => {
+                let out = lines
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, l)| types.is_empty() || types[0] == l.line_type())
+                    .map(|(idx, l)| {
+                        let (start, end) = (l.start, l.end);
+                        let name = l.line_type().name();
+                        format!(
+                            "{:4}: {},{} -> {},{} ({})",
+                            idx, start.0, start.1, end.0, end.1, name
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                Ok(out.join("\n"))
+            }
+            ("types", []) => {
+                let out = LineType::ALL
+                    .iter()
+                    .map(|ty| {
+                        let count = lines.iter().filter(|l| l.line_type() == *ty).count();
+                        format!("{:<10} {}", ty.name(), count)
+                    })
+                    .collect::<Vec<_>>();
+                Ok(out.join("\n"))
             }
+            ("crosses", [_, ..]) => Ok(count_crosses(&selected(&types)).to_string()),
+            ("diagram", _) => {
+                let types = if types.is_empty() {
+                    &LineType::ALL[..4]
+                } else {
+                    &types[..]
+                };
+                Ok(diagram(&selected(types))?)
+            }
+            _ => Err(ExploreError::Usage),
         }
     }
-
-    cross_counter
-        .iter()
-        .filter(|(_, &count)| count >= 1)
-        .count() as i32
 }
 
-pub fn solve_part2(input: &str) -> i32 {
-    let lines = parse_input(input)
+/// Draws the diagram from the puzzle text: how many lines cover each point, `.` for none.
+#[cfg(feature = "std")]
+fn diagram(lines: &[Line]) -> core::result::Result<String, String> {
+    const MAX_SIZE: i32 = 80;
+
+    let width = lines.iter().map(|l| l.max_x() + 1).max().unwrap_or(0);
+    let height = lines.iter().map(|l| l.max_y() + 1).max().unwrap_or(0);
+    if width > MAX_SIZE || height > MAX_SIZE {
+        return Err(format!(
+            "diagram only fits points within 0..{}, found {}x{}",
+            MAX_SIZE, width, height
+        ));
+    }
+
+    let mut counts = vec![vec![0u32; width as usize]; height as usize];
+    for p in lines.iter().flat_map(Line::points) {
+        counts[p.1 as usize][p.0 as usize] += 1;
+    }
+
+    let rows = counts
         .iter()
-        .filter(|l| l.line_type().part2_type())
-        .copied()
+        .map(|row| {
+            row.iter()
+                .map(|&count| match count {
+                    0 => '.',
+                    1..=9 => char::from_digit(count, 10).unwrap_or('+'),
+                    _ => '+',
+                })
+                .collect::<String>()
+        })
         .collect::<Vec<_>>();
+    Ok(rows.join("\n"))
+}
+
+pub fn solve_part1(input: &str) -> Result<i32> {
+    Ok(Day05::part1(&normalize::parse::<Day05>(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<i32> {
+    Ok(Day05::part2(&normalize::parse::<Day05>(input)?))
+}
 
-    let mut cross_counter: HashMap<(i32, i32), i32> = HashMap::new();
-    for i in 0..(lines.len() - 1) {
+fn count_crosses(lines: &[Line]) -> i32 {
+    let mut cross_counter: Map<(i32, i32), i32> = Map::new();
+    for i in 0..lines.len() {
         for j in (i + 1)..lines.len() {
             let crosses = lines[i].cross(&lines[j]);
             for cross in crosses {
@@ -49,7 +183,7 @@ pub fn solve_part2(input: &str) -> i32 {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-struct Line {
+pub struct Line {
     start: (i32, i32),
     end: (i32, i32),
 }
@@ -64,18 +198,44 @@ enum LineType {
 }
 
 impl LineType {
-    fn part1_type(&self) -> bool {
-        match *self {
-            LineType::Horizontal | LineType::Vertical | LineType::Point => true,
-            _ => false,
+    #[cfg(feature = "std")]
+    const ALL: [LineType; 5] = [
+        LineType::Horizontal,
+        LineType::Vertical,
+        LineType::Diagonal,
+        LineType::Point,
+        LineType::Etc,
+    ];
+
+    #[cfg(feature = "std")]
+    fn name(&self) -> &'static str {
+        match self {
+            LineType::Horizontal => "horizontal",
+            LineType::Vertical => "vertical",
+            LineType::Diagonal => "diagonal",
+            LineType::Point => "point",
+            LineType::Etc => "etc",
         }
     }
 
+    #[cfg(feature = "std")]
+    fn from_name(name: &str) -> core::result::Result<Self, String> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|ty| ty.name() == name)
+            .ok_or_else(|| format!("unknown line type `{}`", name))
+    }
+
+    fn part1_type(&self) -> bool {
+        matches!(
+            *self,
+            LineType::Horizontal | LineType::Vertical | LineType::Point
+        )
+    }
+
     fn part2_type(&self) -> bool {
-        match *self {
-            LineType::Etc => false,
-            _ => true,
-        }
+        !matches!(*self, LineType::Etc)
     }
 }
 
@@ -137,10 +297,29 @@ impl Line {
                     false
                 }
             }
-            LineType::Etc => panic!("Etc line type is not supported"),
+            LineType::Etc => {
+                let (dx, dy) = (self.end.0 - self.start.0, self.end.1 - self.start.1);
+                (p.0 - self.start.0) * dy == (p.1 - self.start.1) * dx
+                    && self.min_x() <= p.0
+                    && p.0 <= self.max_x()
+                    && self.min_y() <= p.1
+                    && p.1 <= self.max_y()
+            }
         }
     }
 
+    /// Every point with whole coordinates on the line, from start to end.
+    fn points(&self) -> impl Iterator<Item = (i32, i32)> {
+        let (dx, dy) = (self.end.0 - self.start.0, self.end.1 - self.start.1);
+        let steps = gcd(dx.abs(), dy.abs());
+        let step = match steps {
+            0 => (0, 0),
+            _ => (dx / steps, dy / steps),
+        };
+        let start = self.start;
+        (0..=steps).map(move |i| (start.0 + i * step.0, start.1 + i * step.1))
+    }
+
     fn cross(&self, other: &Line) -> Vec<(i32, i32)> {
         let mut crosses = Vec::new();
 
@@ -162,11 +341,11 @@ impl Line {
             }
             (LineType::Horizontal, LineType::Horizontal) => {
                 let p = cross_horizontal_horizontal(self, other);
-                crosses.extend(p.into_iter());
+                crosses.extend(p);
             }
             (LineType::Vertical, LineType::Vertical) => {
                 let p = cross_vertical_vertical(self, other);
-                crosses.extend(p.into_iter());
+                crosses.extend(p);
             }
             (LineType::Horizontal, LineType::Vertical) => {
                 if let Some(p) = cross_horizontal_vertical(self, other) {
@@ -202,14 +381,25 @@ impl Line {
                 let p = cross_diagonal_diagonal(self, other);
                 crosses.extend(p);
             }
-            (LineType::Etc, _) | (_, LineType::Etc) => {
-                panic!("Etc line type is not supported");
+            (LineType::Etc, _) => {
+                crosses.extend(self.points().filter(|&p| other.has_point(p)));
+            }
+            (_, LineType::Etc) => {
+                crosses.extend(other.points().filter(|&p| self.has_point(p)));
             }
         }
         crosses
     }
 }
 
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
 fn cross_horizontal_horizontal(horizontal1: &Line, horizontal2: &Line) -> Vec<(i32, i32)> {
     let mut crosses = Vec::new();
     if horizontal1.start.1 == horizontal2.start.1 {
@@ -300,45 +490,102 @@ fn cross_diagonal_diagonal(left: &Line, right: &Line) -> Vec<(i32, i32)> {
     crosses
 }
 
-fn parse_input(input: &str) -> Vec<Line> {
+fn parse_input(input: &str) -> Result<Vec<Line>> {
     let mut lines = Vec::new();
-    for line in input.lines() {
-        let tokens = line
-            .split(" -> ")
-            .map(|s| s.split(','))
-            .flatten()
-            .map(|s| s.parse::<i32>().unwrap())
-            .collect::<Vec<_>>();
+    for (idx, line) in input.lines().enumerate() {
+        let [x1, y1, x2, y2] = parse::pattern(Day05::DAY, idx, line, "{},{} -> {},{}")?
+            .map(|token| parse_coordinate(idx, line, token));
+        lines.push(Line::new((x1?, y1?), (x2?, y2?)));
+    }
+    Ok(lines)
+}
 
-        assert_eq!(tokens.len(), 4);
-        lines.push(Line::new((tokens[0], tokens[1]), (tokens[2], tokens[3])));
+/// Parses a coordinate, bounded so that no arithmetic on points can overflow.
+fn parse_coordinate(idx: usize, line: &str, token: &str) -> Result<i32> {
+    let coordinate = parse::number(Day05::DAY, idx, line, token)?;
+    if !(0..=MAX_COORDINATE).contains(&coordinate) {
+        let kind = ParseErrorKind::Unexpected {
+            expected: format!("a coordinate from 0 to {}", MAX_COORDINATE),
+        };
+        return Err(ParseError::at(Day05::DAY, kind, idx, line, token));
     }
-    lines
+    Ok(coordinate)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const SAMPLE_INPUT: &str = r#"0,9 -> 5,9
-8,0 -> 0,8
-9,4 -> 3,4
-2,2 -> 2,1
-7,0 -> 7,4
-6,4 -> 2,0
-0,9 -> 2,9
-3,4 -> 1,4
-0,0 -> 8,8
-5,5 -> 8,2"#;
-
+    #[cfg(feature = "std")]
     #[test]
     fn test_parse() {
-        let lines = parse_input(SAMPLE_INPUT);
+        let lines = parse_input(&crate::examples::input(5, "sample")).unwrap();
         assert_eq!(lines.len(), 10);
         assert_eq!(lines[0], Line::new((0, 9), (5, 9)));
         assert_eq!(lines[9], Line::new((5, 5), (8, 2)));
     }
 
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("0,9 -> 5,9\n8,0 => 0,8").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 11));
+        assert_eq!(error.to_string(), "missing ` -> `");
+
+        let error = parse_input("0,9 -> 5;9").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 11));
+        assert_eq!(error.to_string(), "missing `,`");
+
+        let error = parse_input("0,9 -> 5,a").unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::InvalidNumber);
+        assert_eq!(error.column, 10);
+
+        let error = parse_input("0,9 -> 5,-1").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected a coordinate from 0 to 9999, found `-1`"
+        );
+
+        let error = parse_input("0,9 -> 10000,9").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (8, "10000"));
+    }
+
+    #[test]
+    fn test_max_coordinate() {
+        let input = "0,0 -> 9998,9998\n9998,0 -> 0,9998\n0,9999 -> 9999,9999\n9999,0 -> 9999,9999";
+        assert_eq!(solve_part1(input), Ok(1));
+        assert_eq!(solve_part2(input), Ok(2));
+    }
+
+    #[test]
+    fn test_etc() {
+        let line = Line::new((0, 0), (4, 2));
+        assert_eq!(line.points().collect::<Vec<_>>(), [(0, 0), (2, 1), (4, 2)]);
+        assert!(!line.has_point((1, 0)));
+        assert_eq!(line.cross(&Line::new((2, 0), (2, 3))), [(2, 1)]);
+        assert_eq!(line.cross(&Line::new((1, 0), (1, 3))), []);
+        assert_eq!(Line::new((4, 2), (0, 0)).cross(&line).len(), 3);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_explore_etc() {
+        let lines = parse_input("0,0 -> 4,2\n0,1 -> 4,1").unwrap();
+        assert_eq!(
+            Day05::explore(&lines, "crosses", &["etc", "horizontal"]),
+            Ok("1".to_string())
+        );
+        assert_eq!(
+            Day05::explore(&lines, "diagram", &["etc"]),
+            Ok("1....\n..1..\n....1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_no_lines() {
+        assert_eq!(solve_part1(""), Ok(0));
+        assert_eq!(solve_part2("0,0 -> 1,1"), Ok(0));
+    }
+
     #[test]
     fn test_cross() {
         let line1 = Line::new((7, 0), (7, 4));
@@ -415,30 +662,4 @@ mod tests {
         let crosses = line1.cross(&line2);
         assert_eq!(crosses, vec![]);
     }
-
-    #[test]
-    fn test_part1_sample() {
-        let answer = solve_part1(SAMPLE_INPUT);
-        assert_eq!(answer, 5);
-    }
-
-    #[test]
-    fn test_part1() {
-        let input = include_str!("../input/day_05.txt");
-        let answer = solve_part1(input);
-        assert_eq!(answer, 5442);
-    }
-
-    #[test]
-    fn test_part2_sample() {
-        let answer = solve_part2(SAMPLE_INPUT);
-        assert_eq!(answer, 12);
-    }
-
-    #[test]
-    fn test_part2() {
-        let input = include_str!("../input/day_05.txt");
-        let answer = solve_part2(input);
-        assert_eq!(answer, 19571);
-    }
 }
@@ -0,0 +1,202 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::prelude::*;
+
+pub type Result<T> = core::result::Result<T, ParseError>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    EmptyInput,
+    InvalidNumber,
+    Unexpected {
+        expected: String,
+    },
+    Missing {
+        expected: String,
+    },
+    /// The line parses, but solving would take the answer past what its type can hold.
+    ///
+    /// A property of solving rather than parsing, reported while parsing because parts cannot
+    /// fail; it points at the line where the overflow would first happen.
+    Overflow,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::EmptyInput => write!(f, "input is empty"),
+            ParseErrorKind::InvalidNumber => write!(f, "invalid number"),
+            ParseErrorKind::Unexpected { expected } => write!(f, "expected {}", expected),
+            ParseErrorKind::Missing { expected } => write!(f, "missing {}", expected),
+            ParseErrorKind::Overflow => write!(f, "answer would overflow"),
+        }
+    }
+}
+
+/// An input that could not be parsed, pointing at the offending text.
+///
+/// `line` and `column` are 1-based, `column` counts characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub kind: ParseErrorKind,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub source_line: String,
+}
+
+impl ParseError {
+    /// `token` must be a subslice of `source_line`, which is how its column is found.
+    pub(crate) fn at(
+        day: u8,
+        kind: ParseErrorKind,
+        line_idx: usize,
+        source_line: &str,
+        token: &str,
+    ) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(source_line.as_ptr() as usize)
+            .filter(|&offset| offset + token.len() <= source_line.len())
+            .unwrap_or(0);
+
+        Self {
+            day,
+            kind,
+            line: line_idx + 1,
+            column: source_line[..offset].chars().count() + 1,
+            text: token.to_string(),
+            source_line: source_line.to_string(),
+        }
+    }
+
+    /// Points just past the end of `source_line`, for something that should have followed.
+    pub(crate) fn end_of_line(day: u8, expected: &str, line_idx: usize, source_line: &str) -> Self {
+        let kind = ParseErrorKind::Missing {
+            expected: expected.to_string(),
+        };
+        Self::at(
+            day,
+            kind,
+            line_idx,
+            source_line,
+            &source_line[source_line.len()..],
+        )
+    }
+
+    pub(crate) fn empty_input(day: u8) -> Self {
+        Self::at(day, ParseErrorKind::EmptyInput, 0, "", "")
+    }
+
+    /// The error with the source line and a caret under the offending text.
+    pub fn render(&self) -> String {
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        let width = self.text.chars().count().max(1);
+
+        format!(
+            "error: {}\n{}--> day {:02}, line {}, column {}\n{} |\n{} | {}\n{} | {}{}",
+            self,
+            gutter,
+            self.day,
+            self.line,
+            self.column,
+            gutter,
+            line_number,
+            self.source_line,
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(width),
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if !self.text.is_empty() {
+            write!(f, ", found `{}`", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Failure of a solver reading its input from a stream rather than a string.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "failed to read input: {}", e),
+            ReadError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadError::Io(e) => Some(e),
+            ReadError::Parse(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for ReadError {
+    fn from(error: io::Error) -> Self {
+        ReadError::Io(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<ParseError> for ReadError {
+    fn from(error: ParseError) -> Self {
+        ReadError::Parse(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_column() {
+        let line = "forward x5";
+        let error = parse::number::<u32>(2, 3, line, &line[8..]).unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::InvalidNumber);
+        assert_eq!((error.line, error.column), (4, 9));
+        assert_eq!(error.text, "x5");
+
+        let error = ParseError::end_of_line(2, "value", 0, "up");
+        assert_eq!((error.line, error.column), (1, 3));
+        assert_eq!(error.to_string(), "missing value");
+    }
+
+    #[test]
+    fn test_render() {
+        let line = "0,9 -> 5;9";
+        let error = parse::number::<i32>(5, 11, line, &line[7..]).unwrap_err();
+        assert_eq!(
+            error.render(),
+            "error: invalid number, found `5;9`
+  --> day 05, line 12, column 8
+   |
+12 | 0,9 -> 5;9
+   |        ^^^"
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::manifest::{Manifest, ManifestError};
+use crate::verify::{check_input, Check};
+use crate::{Answer, DynSolution};
+
+/// A puzzle example stored as `input/examples/day_XX/<name>.txt`.
+///
+/// Its expected answers live next to it in `answers.toml`, keyed `[day_XX.<name>]`.
+#[derive(Debug, Clone)]
+pub struct Example {
+    pub day: u8,
+    pub name: String,
+    pub input: String,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Example {
+    pub fn expected(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            _ => self.part2.as_ref(),
+        }
+    }
+}
+
+pub fn examples_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("input")
+        .join("examples")
+}
+
+pub fn day_dir(day: u8) -> PathBuf {
+    examples_dir().join(format!("day_{:02}", day))
+}
+
+/// Every example for `day`, sorted by name; a day without examples has none.
+pub fn load(day: u8) -> Result<Vec<Example>, ManifestError> {
+    let dir = day_dir(day);
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(ManifestError::Io(dir, e)),
+    };
+
+    let mut paths = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| ManifestError::Io(dir.clone(), e))?;
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == "txt"));
+    paths.sort();
+
+    let answers = Manifest::load(&dir.join("answers.toml"))?;
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let name = path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned();
+            let input =
+                std::fs::read_to_string(&path).map_err(|e| ManifestError::Io(path.clone(), e))?;
+            Ok(Example {
+                day,
+                part1: answers.expected(day, &name, 1).cloned(),
+                part2: answers.expected(day, &name, 2).cloned(),
+                name,
+                input,
+            })
+        })
+        .collect()
+}
+
+/// Loads a single example by name, panicking if it is missing. Meant for tests.
+pub fn input(day: u8, name: &str) -> String {
+    let path = day_dir(day).join(format!("{}.txt", name));
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("failed to read example `{}`: {}", path.display(), e))
+}
+
+/// Runs both parts of `day` on each of its examples.
+pub fn check(day: &dyn DynSolution) -> Result<Vec<Check>, ManifestError> {
+    let mut checks = Vec::new();
+    for example in load(day.day())? {
+        let expected = [example.part1, example.part2];
+        checks.extend(check_input(day, &example.name, Ok(example.input), expected));
+    }
+    Ok(checks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::Status;
+    use crate::SOLUTIONS;
+
+    #[test]
+    fn test_examples() {
+        for day in SOLUTIONS {
+            assert!(
+                !load(day.day()).unwrap().is_empty(),
+                "day {} has no examples",
+                day.day()
+            );
+
+            for check in check(*day).unwrap() {
+                if check.expected.is_none() {
+                    continue;
+                }
+                assert_eq!(
+                    check.status(),
+                    Status::Pass,
+                    "day {} part {} on example `{}`: expected {:?}, got {:?}",
+                    check.day,
+                    check.part,
+                    check.input,
+                    check.expected,
+                    check.actual
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_load() {
+        let examples = load(4).unwrap();
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].name, "sample");
+        assert_eq!(examples[0].expected(2), Some(&Answer::from(1924u32)));
+        assert!(load(25).unwrap().is_empty());
+    }
+}
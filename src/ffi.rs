@@ -0,0 +1,330 @@
+//! C ABI for calling solutions from other languages, enabled by the `ffi` feature.
+//!
+//! Build the shared library with
+//! `cargo rustc --lib --release --features ffi --crate-type cdylib` and include
+//! `include/aoc.h`, which is generated from this module by `header`. `include/example.c` calls
+//! it, and `cargo test --features ffi -- --ignored` builds and runs that. The manifest does not
+//! list `cdylib` as a crate type, since that would also build one without `std`, which has no
+//! panic handler to link.
+
+use std::ffi::{c_char, CStr};
+use std::ptr;
+
+use crate::runner::{Failure, Job, Runner};
+use crate::solution;
+
+pub const AOC_OK: i32 = 0;
+pub const AOC_ERR_NULL_POINTER: i32 = -1;
+pub const AOC_ERR_UNKNOWN_DAY: i32 = -2;
+pub const AOC_ERR_INVALID_PART: i32 = -3;
+pub const AOC_ERR_INVALID_UTF8: i32 = -4;
+pub const AOC_ERR_PARSE: i32 = -5;
+pub const AOC_ERR_PANIC: i32 = -6;
+pub const AOC_ERR_BUFFER_TOO_SMALL: i32 = -7;
+
+/// Every error code with its name in the header and its description.
+const CODES: &[(i32, &str, &CStr)] = &[
+    (AOC_OK, "AOC_OK", c"success"),
+    (
+        AOC_ERR_NULL_POINTER,
+        "AOC_ERR_NULL_POINTER",
+        c"a required pointer was null",
+    ),
+    (
+        AOC_ERR_UNKNOWN_DAY,
+        "AOC_ERR_UNKNOWN_DAY",
+        c"the day is not implemented",
+    ),
+    (
+        AOC_ERR_INVALID_PART,
+        "AOC_ERR_INVALID_PART",
+        c"the part is not 1 or 2",
+    ),
+    (
+        AOC_ERR_INVALID_UTF8,
+        "AOC_ERR_INVALID_UTF8",
+        c"the input is not valid UTF-8",
+    ),
+    (
+        AOC_ERR_PARSE,
+        "AOC_ERR_PARSE",
+        c"the input could not be parsed",
+    ),
+    (AOC_ERR_PANIC, "AOC_ERR_PANIC", c"the solver panicked"),
+    (
+        AOC_ERR_BUFFER_TOO_SMALL,
+        "AOC_ERR_BUFFER_TOO_SMALL",
+        c"the output buffer is too small",
+    ),
+];
+
+/// Solves one part of one day.
+///
+/// On success writes the answer to `out` as a NUL terminated string and returns `AOC_OK`.
+/// On `AOC_ERR_PARSE` and `AOC_ERR_PANIC` writes the error message instead, truncated to fit.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes (or may be null if `len` is 0), and `out`
+/// must point to `out_len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    len: usize,
+    out: *mut c_char,
+    out_len: usize,
+) -> i32 {
+    if out.is_null() || (input.is_null() && len > 0) {
+        return AOC_ERR_NULL_POINTER;
+    }
+    let Some(solution) = solution(day) else {
+        return AOC_ERR_UNKNOWN_DAY;
+    };
+    if part != 1 && part != 2 {
+        return AOC_ERR_INVALID_PART;
+    }
+    let bytes = if len == 0 {
+        &[][..]
+    } else {
+        std::slice::from_raw_parts(input, len)
+    };
+    let Ok(input) = std::str::from_utf8(bytes) else {
+        return AOC_ERR_INVALID_UTF8;
+    };
+
+    let job = Job {
+        day: solution,
+        name: "ffi".to_string(),
+        input: Ok(input.to_string()),
+        parts: vec![part],
+    };
+    let run = Runner::new().jobs(1).run(&[job]).remove(0);
+    match &run.parts[0].answer {
+        Ok(answer) => match write_str(&answer.to_string(), out, out_len, false) {
+            true => AOC_OK,
+            false => AOC_ERR_BUFFER_TOO_SMALL,
+        },
+        Err(Failure::Parse(error)) => {
+            let message = format!("line {}, column {}: {}", error.line, error.column, error);
+            write_str(&message, out, out_len, true);
+            AOC_ERR_PARSE
+        }
+        Err(failure) => {
+            write_str(&failure.to_string(), out, out_len, true);
+            AOC_ERR_PANIC
+        }
+    }
+}
+
+/// A static, NUL terminated description of an error code.
+#[no_mangle]
+pub extern "C" fn aoc_strerror(code: i32) -> *const c_char {
+    CODES
+        .iter()
+        .find(|(c, _, _)| *c == code)
+        .map_or(c"unknown error code", |(_, _, description)| description)
+        .as_ptr()
+}
+
+/// Copies `text` and a NUL into `out`, returning whether it fit. With `truncate` as much as
+/// fits is written instead of nothing, cut at a character boundary so it stays valid UTF-8.
+unsafe fn write_str(text: &str, out: *mut c_char, out_len: usize, truncate: bool) -> bool {
+    let fits = text.len() < out_len;
+    if out_len == 0 || (!fits && !truncate) {
+        return false;
+    }
+    let mut len = text.len().min(out_len - 1);
+    while !text.is_char_boundary(len) {
+        len -= 1;
+    }
+    ptr::copy_nonoverlapping(text.as_ptr().cast(), out, len);
+    *out.add(len) = 0;
+    fits
+}
+
+/// The C header declaring this module's functions and error codes.
+pub fn header() -> String {
+    let mut header = String::from(
+        "/* Generated from src/ffi.rs, do not edit. */\n\
+         #ifndef AOC_H\n\
+         #define AOC_H\n\
+         \n\
+         #include <stddef.h>\n\
+         #include <stdint.h>\n\
+         \n\
+         #ifdef __cplusplus\n\
+         extern \"C\" {\n\
+         #endif\n\
+         \n",
+    );
+    for (code, name, description) in CODES {
+        let description = description.to_string_lossy();
+        header.push_str(&format!(
+            "/* {} */\n#define {} {}\n",
+            description, name, code
+        ));
+    }
+    header.push_str(
+        "\n\
+         /* Solves one part of one day, writing the NUL terminated answer to `out`.\n\
+         \x20* On AOC_ERR_PARSE and AOC_ERR_PANIC the error message is written instead. */\n\
+         int32_t aoc_solve(uint8_t day, uint8_t part, const uint8_t *input, size_t len,\n\
+         \x20                 char *out, size_t out_len);\n\
+         \n\
+         /* A static description of an error code. */\n\
+         const char *aoc_strerror(int32_t code);\n\
+         \n\
+         #ifdef __cplusplus\n\
+         }\n\
+         #endif\n\
+         \n\
+         #endif /* AOC_H */\n",
+    );
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    // declared again so the calls below go through the exported C symbols
+    extern "C" {
+        #[link_name = "aoc_solve"]
+        fn c_aoc_solve(
+            day: u8,
+            part: u8,
+            input: *const u8,
+            len: usize,
+            out: *mut c_char,
+            out_len: usize,
+        ) -> i32;
+        #[link_name = "aoc_strerror"]
+        fn c_aoc_strerror(code: i32) -> *const c_char;
+    }
+
+    fn solve(day: u8, part: u8, input: &str, out_len: usize) -> (i32, String) {
+        let mut out = vec![0 as c_char; out_len.max(1)];
+        let code = unsafe {
+            c_aoc_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr(),
+                out_len,
+            )
+        };
+        let text = unsafe { CStr::from_ptr(out.as_ptr()) };
+        (code, text.to_string_lossy().into_owned())
+    }
+
+    #[test]
+    fn test_solve() {
+        let sample = crate::examples::input(1, "sample");
+        assert_eq!(solve(1, 1, &sample, 64), (AOC_OK, "7".to_string()));
+        assert_eq!(solve(1, 2, &sample, 64), (AOC_OK, "5".to_string()));
+        assert_eq!(solve(1, 1, &sample, 1).0, AOC_ERR_BUFFER_TOO_SMALL);
+        assert_eq!(solve(99, 1, &sample, 64).0, AOC_ERR_UNKNOWN_DAY);
+        assert_eq!(solve(1, 3, &sample, 64).0, AOC_ERR_INVALID_PART);
+
+        let (code, message) = solve(1, 1, "1\nx", 64);
+        assert_eq!(code, AOC_ERR_PARSE);
+        assert_eq!(message, "line 2, column 1: invalid number, found `x`");
+        assert_eq!(solve(1, 1, "1\nx", 8).1, "line 2,");
+
+        // the `é` would be cut in half one byte later, so it is left out whole
+        let (code, message) = solve(1, 1, "1\né", 64);
+        assert_eq!(message, "line 2, column 1: invalid number, found `é`");
+        assert_eq!(code, AOC_ERR_PARSE);
+        let cut = message.find('é').unwrap();
+        assert_eq!(solve(1, 1, "1\né", cut + 2).1, &message[..cut]);
+
+        let invalid = [0xff, 0xfe];
+        let mut out = [0 as c_char; 8];
+        let code = unsafe { c_aoc_solve(1, 1, invalid.as_ptr(), 2, out.as_mut_ptr(), 8) };
+        assert_eq!(code, AOC_ERR_INVALID_UTF8);
+        let code = unsafe { c_aoc_solve(1, 1, ptr::null(), 2, out.as_mut_ptr(), 8) };
+        assert_eq!(code, AOC_ERR_NULL_POINTER);
+        let code = unsafe { c_aoc_solve(1, 1, ptr::null(), 0, out.as_mut_ptr(), 8) };
+        assert_eq!(code, AOC_OK);
+
+        let message = unsafe { CStr::from_ptr(c_aoc_strerror(AOC_ERR_PANIC)) };
+        assert_eq!(message.to_str(), Ok("the solver panicked"));
+    }
+
+    #[test]
+    fn test_strerror() {
+        for (code, _, description) in CODES {
+            let message = unsafe { CStr::from_ptr(aoc_strerror(*code)) };
+            assert_eq!(message, *description);
+        }
+        let message = unsafe { CStr::from_ptr(aoc_strerror(42)) };
+        assert_eq!(message, c"unknown error code");
+    }
+
+    /// Builds the shared library and runs `include/example.c` against it, if there is a C
+    /// compiler to build it with. Run it with `cargo test --features ffi -- --ignored`.
+    #[test]
+    #[ignore = "builds the library with a nested cargo and a C compiler"]
+    fn test_c_caller() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        // a target directory of its own, as the one running this test is locked
+        let target = root.join("target/ffi");
+        let status = std::process::Command::new(env!("CARGO"))
+            .args(["rustc", "--lib", "--quiet", "--features", "ffi"])
+            .args(["--crate-type", "cdylib", "--target-dir"])
+            .arg(&target)
+            .current_dir(root)
+            .status()
+            .unwrap();
+        assert!(status.success(), "building the library failed");
+
+        let lib_dir = target.join("debug");
+        let program = target.join("example");
+        let compiled = std::process::Command::new("cc")
+            .arg(root.join("include/example.c"))
+            .arg("-I")
+            .arg(root.join("include"))
+            .arg("-L")
+            .arg(&lib_dir)
+            .args(["-ladvent_of_code_2021", "-o"])
+            .arg(&program)
+            .status();
+        let Ok(compiled) = compiled else {
+            eprintln!("skipping test_c_caller: no C compiler `cc` found");
+            return;
+        };
+        assert!(compiled.success(), "compiling example.c failed");
+
+        let output = std::process::Command::new(&program)
+            .env("LD_LIBRARY_PATH", &lib_dir)
+            .env("DYLD_LIBRARY_PATH", &lib_dir)
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "day 1 part 1: 7\n\
+             day 1 part 2: 5\n\
+             day 1 part 1: line 2, column 1: invalid number, found `x`\n\
+             day 99 part 1: the day is not implemented\n"
+        );
+        assert!(output.status.success());
+    }
+
+    #[test]
+    fn test_header() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("include/aoc.h");
+        if std::env::var_os("AOC_BLESS").is_some() {
+            std::fs::write(&path, header()).unwrap();
+        }
+        let current = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            current == header(),
+            "`{}` is out of date, regenerate it with `AOC_BLESS=1 cargo test --features ffi`",
+            path.display()
+        );
+    }
+}
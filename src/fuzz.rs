@@ -0,0 +1,294 @@
+//! Fuzzing each day's parser and solvers: inputs mutated from a seed corpus must either parse
+//! and solve, or fail with a parse error, and never panic.
+//!
+//! Overflow is only caught with overflow checks enabled, as in the default debug build.
+
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use crate::error::ReadError;
+use crate::generate::Rng;
+use crate::input::{self, InputLoader};
+use crate::panic_hook::{self, with_panic_hook};
+use crate::{day_01, day_02, day_03, day_04, day_05, examples, DynSolution};
+
+/// Inputs are cut down to this many bytes, so mutations cannot grow them without bound.
+pub const MAX_LEN: usize = 1 << 16;
+
+/// Numbers swapped in for others, at and around the edges of the types solvers parse into.
+const NUMBERS: &[&str] = &[
+    "0",
+    "1",
+    "-1",
+    "255",
+    "65536",
+    "2147483647",
+    "2147483648",
+    "-2147483648",
+    "4294967295",
+    "4294967296",
+    "18446744073709551615",
+];
+
+/// Bytes inserted at random: the punctuation every day's input is made of.
+const BYTES: &[u8] = b"0123456789 ,->\n\r\tforwardupdn";
+
+/// An input the target panicked on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crash {
+    pub input: Vec<u8>,
+    /// Where and why it panicked, such as `panicked at src/day_05.rs:189:17: ...`.
+    pub message: String,
+}
+
+/// The fuzz target: parses `data` and, if it parses, solves both parts, then runs the day's
+/// own entry points on it too.
+pub fn target(day: &dyn DynSolution, data: &[u8]) {
+    if let Ok(input) = std::str::from_utf8(data) {
+        if let Ok(parsed) = day.parse(input) {
+            day.part1(parsed.as_ref());
+            day.part2(parsed.as_ref());
+        }
+    }
+    entry_points(day.day(), data);
+}
+
+/// Runs a day's `solve_part1` and `solve_part2` on `data` as text, and its streaming readers
+/// on the raw bytes.
+fn entry_points(day: u8, data: &[u8]) {
+    let text = std::str::from_utf8(data).ok();
+    match day {
+        1 => {
+            agree(data, day_01::solve_part1, |r| day_01::solve_part1_reader(r));
+            agree(data, day_01::solve_part2, |r| day_01::solve_part2_reader(r));
+        }
+        2 => {
+            agree(data, day_02::solve_part1, |r| day_02::solve_part1_reader(r));
+            agree(data, day_02::solve_part2, |r| day_02::solve_part2_reader(r));
+        }
+        3 => {
+            agree(data, day_03::solve_part1, |r| day_03::solve_part1_reader(r));
+            let _ = text.map(day_03::solve_part2);
+        }
+        4 => {
+            let _ = text.map(day_04::solve_part1);
+            let _ = text.map(day_04::solve_part2);
+        }
+        5 => {
+            let _ = text.map(day_05::solve_part1);
+            let _ = text.map(day_05::solve_part2);
+        }
+        _ => {}
+    }
+}
+
+/// Checks a streaming reader against the `&str` entry point it mirrors: on UTF-8 both give
+/// the same answer or both fail, and anything else must fail with a `ReadError`.
+fn agree<T: PartialEq + fmt::Debug>(
+    data: &[u8],
+    solve: fn(&str) -> crate::Result<T>,
+    read: impl Fn(&[u8]) -> Result<T, ReadError>,
+) {
+    let read = read(data);
+    match std::str::from_utf8(data) {
+        Ok(text) => match (solve(text), read) {
+            (Ok(solved), Ok(read)) => assert_eq!(solved, read, "reader disagrees"),
+            (Err(_), Err(_)) => {}
+            (solved, read) => panic!("reader disagrees: {:?} but {:?}", solved, read),
+        },
+        Err(_) => assert!(read.is_err(), "reader accepted invalid UTF-8: {:?}", read),
+    }
+}
+
+/// Runs the target on `data`, returning where it panicked if it did.
+pub fn check(day: &dyn DynSolution, data: &[u8]) -> Result<(), String> {
+    with_panic_hook(|| run(day, data))
+}
+
+/// Runs the target on `runs` inputs mutated from `corpus`, returning one crash per distinct
+/// panic, with the shortest input found for it.
+pub fn fuzz(day: &dyn DynSolution, corpus: &[Vec<u8>], seed: u64, runs: usize) -> Vec<Crash> {
+    let mut rng = Rng::new(seed);
+    let mut crashes = Vec::<Crash>::new();
+    with_panic_hook(|| {
+        for _ in 0..runs {
+            let data = mutate(&mut rng, corpus);
+            let Err(message) = run(day, &data) else {
+                continue;
+            };
+            match crashes.iter_mut().find(|crash| crash.message == message) {
+                Some(crash) if crash.input.len() > data.len() => crash.input = data,
+                Some(_) => {}
+                None => crashes.push(Crash {
+                    input: data,
+                    message,
+                }),
+            }
+        }
+    });
+    crashes
+}
+
+/// A random corpus entry with one to four random mutations applied.
+pub fn mutate(rng: &mut Rng, corpus: &[Vec<u8>]) -> Vec<u8> {
+    let mut data = if corpus.is_empty() {
+        Vec::new()
+    } else {
+        rng.pick(corpus).clone()
+    };
+    for _ in 0..rng.range(1..5) {
+        let at = rng.range(0..data.len() as u32 + 1) as usize;
+        let len = rng.range(0..(data.len() - at).min(16) as u32 + 1) as usize;
+        match rng.range(0..6) {
+            0 if at < data.len() => data[at] = rng.range(0..256) as u8,
+            1 => data.insert(at, *rng.pick(BYTES)),
+            2 => {
+                data.drain(at..at + len);
+            }
+            3 => {
+                let copy = data[at..at + len].to_vec();
+                data.splice(at..at, copy);
+            }
+            4 => {
+                let digits = data[at..].iter().take_while(|b| b.is_ascii_digit()).count();
+                let number = rng.pick(NUMBERS).bytes();
+                data.splice(at..at + digits, number);
+            }
+            5 if !corpus.is_empty() => {
+                let other = rng.pick(corpus);
+                let from = rng.range(0..other.len() as u32 + 1) as usize;
+                data.truncate(at);
+                data.extend_from_slice(&other[from..]);
+            }
+            _ => data.truncate(at),
+        }
+    }
+    data.truncate(MAX_LEN);
+    data
+}
+
+/// Where past crashes are kept as `<name>.txt`, so every later run starts from them.
+pub fn corpus_dir(day: u8) -> PathBuf {
+    fuzz_dir().join("corpus").join(format!("day_{:02}", day))
+}
+
+/// Where `save` writes new crashes.
+pub fn crashes_dir(day: u8) -> PathBuf {
+    fuzz_dir().join("crashes").join(format!("day_{:02}", day))
+}
+
+/// The inputs mutations start from: the day's examples, every input `loader` finds and the
+/// saved corpus.
+pub fn seed_corpus(day: u8, loader: &InputLoader) -> Vec<Vec<u8>> {
+    let examples = examples::load(day).unwrap_or_default();
+    let inputs = loader
+        .discover(day)
+        .into_iter()
+        .filter_map(|name| loader.named(&name).load(day).ok());
+    let saved = input::inputs_in(&corpus_dir(day))
+        .into_iter()
+        .filter_map(|(_, path)| std::fs::read(path).ok());
+
+    examples
+        .into_iter()
+        .map(|example| example.input.into_bytes())
+        .chain(inputs.map(String::into_bytes))
+        .chain(saved)
+        .collect()
+}
+
+/// Writes a crash's input to `crashes_dir`, named after a hash of it.
+pub fn save(day: u8, crash: &Crash) -> std::io::Result<PathBuf> {
+    let dir = crashes_dir(day);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{:016x}.txt", fnv1a(&crash.input)));
+    std::fs::write(&path, &crash.input)?;
+    Ok(path)
+}
+
+fn fuzz_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz")
+}
+
+fn run(day: &dyn DynSolution, data: &[u8]) -> Result<(), String> {
+    panic::catch_unwind(AssertUnwindSafe(|| target(day, data)))
+        .map_err(|_| panic_hook::take_last().unwrap_or_else(|| "panicked".to_string()))
+}
+
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SOLUTIONS;
+
+    #[test]
+    fn test_corpus() {
+        for day in SOLUTIONS {
+            for (name, path) in input::inputs_in(&corpus_dir(day.day())) {
+                let data = std::fs::read(&path).unwrap();
+                if let Err(message) = check(*day, &data) {
+                    panic!("day {} corpus `{}`: {}", day.day(), name, message);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fuzz() {
+        for day in SOLUTIONS {
+            let corpus = examples::load(day.day())
+                .unwrap()
+                .into_iter()
+                .map(|example| example.input.into_bytes())
+                .collect::<Vec<_>>();
+            let crashes = fuzz(*day, &corpus, 0, 2000);
+            if let Some(crash) = crashes.first() {
+                panic!(
+                    "day {}: {}\n{}",
+                    day.day(),
+                    crash.message,
+                    String::from_utf8_lossy(&crash.input)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_entry_points() {
+        let odd: [&[u8]; 4] = [b"1\n\xff\n", b"\xff", b"", b"0\r\n\r\n"];
+        for day in SOLUTIONS {
+            let examples = examples::load(day.day()).unwrap();
+            let inputs = examples.iter().map(|example| example.input.as_bytes());
+            for data in inputs.chain(odd) {
+                if let Err(message) = check(*day, data) {
+                    panic!("day {} on {:?}: {}", day.day(), data, message);
+                }
+            }
+        }
+
+        let read = day_01::solve_part1_reader(&b"1\n\xff\n"[..]);
+        assert!(matches!(read, Err(ReadError::Io(_))), "{:?}", read);
+
+        let _ = with_panic_hook(|| panic::catch_unwind(|| agree(b"1", |_| Ok(1), |_| Ok(2))));
+        let recorded = panic_hook::take_last().unwrap();
+        assert!(recorded.contains("reader disagrees"), "{}", recorded);
+    }
+
+    #[test]
+    fn test_mutate() {
+        let corpus = vec![b"1,2 -> 3,4\n".to_vec()];
+        let mut rng = Rng::new(1);
+        let mutated = (0..100)
+            .map(|_| mutate(&mut rng, &corpus))
+            .collect::<Vec<_>>();
+        assert!(mutated.iter().any(|data| *data != corpus[0]));
+        assert!(mutated.iter().all(|data| data.len() <= MAX_LEN));
+        assert!(mutate(&mut rng, &[]).len() <= 4);
+    }
+}
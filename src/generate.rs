@@ -0,0 +1,182 @@
+//! Random puzzle inputs, reproducible from a seed, for testing solvers against `oracle`.
+//!
+//! Inputs are valid by the puzzle's rules but kept small, so overlaps, ties and empty inputs
+//! come up far more often than in real inputs.
+
+use std::ops::Range;
+
+/// A small seedable pseudo random number generator (SplitMix64).
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which must not be empty.
+    pub fn range(&mut self, range: Range<u32>) -> u32 {
+        assert!(!range.is_empty(), "empty range {:?}", range);
+        range.start + (self.next_u64() % u64::from(range.end - range.start)) as u32
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..items.len() as u32) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..i as u32 + 1) as usize);
+        }
+    }
+}
+
+/// A random input for `day`, or `None` if there is no generator for it.
+pub fn input(day: u8, rng: &mut Rng) -> Option<String> {
+    let input = match day {
+        1 => depths(rng),
+        2 => commands(rng),
+        3 => diagnostic(rng),
+        4 => bingo(rng),
+        5 => vents(rng),
+        _ => return None,
+    };
+    Some(input)
+}
+
+/// Day 1: sonar depths drifting up and down.
+pub fn depths(rng: &mut Rng) -> String {
+    let mut depth = rng.range(0..5000);
+    let lines = (0..rng.range(0..200)).map(|_| {
+        depth = (depth + rng.range(0..41)).saturating_sub(20);
+        depth.to_string()
+    });
+    join_lines(lines)
+}
+
+/// Day 2: a submarine command script.
+pub fn commands(rng: &mut Rng) -> String {
+    let lines = (0..rng.range(0..100)).map(|_| {
+        let command = rng.pick(&["forward", "up", "down"]);
+        format!("{} {}", command, rng.range(1..10))
+    });
+    join_lines(lines)
+}
+
+/// Day 3: binary numbers of equal width, distinct or drawn with repeats, so that the bit
+/// criteria sometimes cannot narrow them down to one.
+pub fn diagnostic(rng: &mut Rng) -> String {
+    let width = rng.range(1..13);
+    let mut values = (0..1u32 << width).collect::<Vec<_>>();
+    rng.shuffle(&mut values);
+    let count = rng.range(1..values.len().min(200) as u32 + 1) as usize;
+    let values = if rng.range(0..2) == 0 {
+        values[..count].to_vec()
+    } else {
+        (0..count).map(|_| *rng.pick(&values[..count])).collect()
+    };
+    let lines = values
+        .iter()
+        .map(|value| format!("{:0width$b}", value, width = width as usize));
+    join_lines(lines)
+}
+
+/// Day 4: a shuffled deck, possibly too short for every board to win, and boards of
+/// distinct numbers taken from it.
+pub fn bingo(rng: &mut Rng) -> String {
+    let max = rng.range(25..100);
+    let mut deck = (0..max).collect::<Vec<_>>();
+    rng.shuffle(&mut deck);
+
+    let drawn = rng.range(1..max + 1) as usize;
+    let mut input = deck[..drawn]
+        .iter()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    input.push('\n');
+
+    for _ in 0..rng.range(0..10) {
+        rng.shuffle(&mut deck);
+        input.push('\n');
+        for row in deck[..25].chunks(5) {
+            let row = row.iter().map(|n| format!("{:>2}", n)).collect::<Vec<_>>();
+            input.push_str(&row.join(" "));
+            input.push('\n');
+        }
+    }
+    input
+}
+
+/// Day 5: horizontal, vertical and diagonal vent lines, plus single points, on a grid small
+/// enough for many of them to overlap.
+pub fn vents(rng: &mut Rng) -> String {
+    let size = rng.range(5..20) as i32;
+    let lines = (0..rng.range(0..50)).map(|_| {
+        let start = (
+            rng.range(0..size as u32) as i32,
+            rng.range(0..size as u32) as i32,
+        );
+        let (dx, dy) = *rng.pick(&[(1, 0), (0, 1), (1, 1), (1, -1), (0, 0)]);
+        let sign = *rng.pick(&[1, -1]);
+        let (dx, dy) = (dx * sign, dy * sign);
+        // the longest line in that direction which stays on the grid
+        let room = |p: i32, d: i32| match d {
+            1 => size - 1 - p,
+            -1 => p,
+            _ => size,
+        };
+        let len = rng.range(0..room(start.0, dx).min(room(start.1, dy)) as u32 + 1) as i32;
+        let end = (start.0 + dx * len, start.1 + dy * len);
+        format!("{},{} -> {},{}", start.0, start.1, end.0, end.1)
+    });
+    join_lines(lines)
+}
+
+fn join_lines(lines: impl Iterator<Item = String>) -> String {
+    lines.map(|line| line + "\n").collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SOLUTIONS;
+
+    #[test]
+    fn test_rng() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        assert_eq!(a.next_u64(), b.next_u64());
+        assert_ne!(a.next_u64(), Rng::new(8).next_u64());
+        assert!((0..1000).all(|_| (3..9).contains(&a.range(3..9))));
+
+        let mut items = (0..20).collect::<Vec<_>>();
+        a.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_inputs_parse() {
+        for day in SOLUTIONS {
+            for seed in 0..200 {
+                // days scaffolded since have no generator yet
+                let Some(input) = input(day.day(), &mut Rng::new(seed)) else {
+                    continue;
+                };
+                if let Err(error) = day.parse(&input) {
+                    panic!("day {} seed {}: {}\n{}", day.day(), seed, error, input);
+                }
+            }
+        }
+        assert!(input(25, &mut Rng::new(0)).is_none());
+    }
+}
@@ -0,0 +1,385 @@
+use std::fmt;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Method::Get => write!(f, "GET"),
+            Method::Post => write!(f, "POST"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+impl Request {
+    pub fn new(method: Method, url: impl Into<String>) -> Self {
+        Self {
+            method,
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn form(mut self, fields: &[(&str, &str)]) -> Self {
+        let body = fields
+            .iter()
+            .map(|(name, value)| format!("{}={}", url_encode(name), url_encode(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+        self.body = Some(body);
+        self.header("Content-Type", "application/x-www-form-urlencoded")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpError(pub String);
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+/// Sends requests for the puzzle client, so tests can swap in a stand-in.
+pub trait HttpBackend {
+    fn send(&self, request: &Request) -> Result<Response, HttpError>;
+}
+
+/// The default backend: plain `http://` over a `TcpStream`, `https://` through `curl`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdHttp;
+
+impl HttpBackend for StdHttp {
+    fn send(&self, request: &Request) -> Result<Response, HttpError> {
+        if request.url.starts_with("http://") {
+            send_plain(request)
+        } else if request.url.starts_with("https://") {
+            send_curl(request)
+        } else {
+            Err(HttpError(format!("unsupported url `{}`", request.url)))
+        }
+    }
+}
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+fn send_plain(request: &Request) -> Result<Response, HttpError> {
+    let rest = &request.url["http://".len()..];
+    let (authority, path) = match rest.find('/') {
+        Some(idx) => (&rest[..idx], &rest[idx..]),
+        None => (rest, "/"),
+    };
+    let address = if authority.contains(':') {
+        authority.to_string()
+    } else {
+        format!("{}:80", authority)
+    };
+    let error = |e: std::io::Error| HttpError(format!("{} {}: {}", request.method, request.url, e));
+
+    let mut stream = TcpStream::connect(&address).map_err(error)?;
+    stream.set_read_timeout(Some(TIMEOUT)).map_err(error)?;
+
+    let body = request.body.as_deref().unwrap_or("");
+    let mut head = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nContent-Length: {}\r\n",
+        request.method,
+        path,
+        authority,
+        body.len()
+    );
+    for (name, value) in &request.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes()).map_err(error)?;
+    stream.write_all(body.as_bytes()).map_err(error)?;
+
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw).map_err(error)?;
+    parse_response(&raw)
+}
+
+fn parse_response(raw: &[u8]) -> Result<Response, HttpError> {
+    let raw = String::from_utf8_lossy(raw);
+    let (head, body) = raw
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| HttpError("malformed response: no header terminator".to_string()))?;
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| HttpError("malformed response: bad status line".to_string()))?;
+
+    let chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("transfer-encoding")
+                && value.trim().eq_ignore_ascii_case("chunked")
+        })
+    });
+    let body = if chunked {
+        decode_chunked(body)?
+    } else {
+        body.to_string()
+    };
+
+    Ok(Response { status, body })
+}
+
+fn decode_chunked(mut body: &str) -> Result<String, HttpError> {
+    let malformed = || HttpError("malformed chunked body".to_string());
+    let mut decoded = String::new();
+    loop {
+        let (size, rest) = body.split_once("\r\n").ok_or_else(malformed)?;
+        let size = usize::from_str_radix(size.split(';').next().unwrap().trim(), 16)
+            .map_err(|_| malformed())?;
+        if size == 0 {
+            return Ok(decoded);
+        }
+        let chunk = rest.get(..size).ok_or_else(malformed)?;
+        decoded.push_str(chunk);
+        body = rest[size..].strip_prefix("\r\n").ok_or_else(malformed)?;
+    }
+}
+
+fn send_curl(request: &Request) -> Result<Response, HttpError> {
+    let mut command = Command::new("curl");
+    command
+        .args(["--silent", "--show-error", "--max-time", "30"])
+        .args(["--request", &request.method.to_string()])
+        .args(["--write-out", "\n%{http_code}"])
+        .args(["--config", "-"])
+        .arg(&request.url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let error = |e: std::io::Error| HttpError(format!("failed to run curl: {}", e));
+    let mut child = command.spawn().map_err(error)?;
+    let mut stdin = child.stdin.take().unwrap();
+    stdin
+        .write_all(curl_config(request).as_bytes())
+        .map_err(error)?;
+    drop(stdin);
+    let output = child.wait_with_output().map_err(error)?;
+    if !output.status.success() {
+        return Err(HttpError(format!(
+            "{} {}: {}",
+            request.method,
+            request.url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout
+        .rsplit_once('\n')
+        .ok_or_else(|| HttpError("curl printed no status".to_string()))?;
+    let status = status
+        .trim()
+        .parse()
+        .map_err(|_| HttpError(format!("curl printed a bad status `{}`", status)))?;
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+/// The headers and body as a curl config file, read from stdin so that the session cookie
+/// never appears in curl's arguments, which any local user can read.
+fn curl_config(request: &Request) -> String {
+    let mut config = String::new();
+    for (name, value) in &request.headers {
+        let header = format!("{}: {}", name, value);
+        config.push_str(&format!("header = {}\n", curl_quote(&header)));
+    }
+    if let Some(body) = &request.body {
+        config.push_str(&format!("data-raw = {}\n", curl_quote(body)));
+    }
+    config
+}
+
+fn curl_quote(text: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn url_encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// A local stand-in server answering each request with the next canned response.
+    ///
+    /// Returns its base url and the raw requests it received.
+    pub(crate) fn serve(responses: Vec<(u16, String)>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let log = Arc::clone(&received);
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let request = read_request(&mut stream);
+                log.lock().unwrap().push(request);
+                let response = format!(
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        (url, received)
+    }
+
+    fn read_request(stream: &mut TcpStream) -> String {
+        let mut raw = Vec::new();
+        let mut buf = [0; 1024];
+        loop {
+            let n = stream.read(&mut buf).unwrap();
+            raw.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&raw);
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Content-Length: "))
+                    .map_or(0, |n| n.trim().parse().unwrap());
+                if body.len() >= length {
+                    return text.into_owned();
+                }
+            }
+            if n == 0 {
+                return text.into_owned();
+            }
+        }
+    }
+
+    #[test]
+    fn test_plain_http() {
+        let (url, received) = serve(vec![(200, "hello".to_string())]);
+        let request = Request::new(Method::Post, format!("{}/submit", url))
+            .header("Cookie", "session=abc")
+            .form(&[("level", "1"), ("answer", "a b&c")]);
+        let response = StdHttp.send(&request).unwrap();
+        assert_eq!(
+            response,
+            Response {
+                status: 200,
+                body: "hello".to_string()
+            }
+        );
+
+        let received = received.lock().unwrap();
+        assert!(received[0].starts_with("POST /submit HTTP/1.1\r\n"));
+        assert!(received[0].contains("\r\nCookie: session=abc\r\n"));
+        assert!(received[0].ends_with("\r\n\r\nlevel=1&answer=a%20b%26c"));
+    }
+
+    #[test]
+    fn test_curl_config() {
+        let mut request = Request::new(Method::Post, "https://example.com")
+            .header("Cookie", "session=abc")
+            .header("X-Quote", "a\"b\\c\n");
+        request.body = Some("answer=1".to_string());
+        assert_eq!(
+            curl_config(&request),
+            "header = \"Cookie: session=abc\"\n\
+             header = \"X-Quote: a\\\"b\\\\c\\n\"\n\
+             data-raw = \"answer=1\"\n"
+        );
+    }
+
+    #[test]
+    fn test_curl() {
+        if Command::new("curl").arg("--version").output().is_err() {
+            return;
+        }
+        // curl takes plain http too, which lets the stand-in server see what it sent
+        let (url, received) = serve(vec![(200, "hello".to_string())]);
+        let request = Request::new(Method::Post, format!("{}/submit", url))
+            .header("Cookie", "session=abc")
+            .form(&[("level", "1"), ("answer", "a b&c")]);
+        let response = send_curl(&request).unwrap();
+        assert_eq!(response.body, "hello");
+
+        let received = received.lock().unwrap();
+        assert!(received[0].starts_with("POST /submit HTTP/1.1\r\n"));
+        assert!(received[0].contains("\r\nCookie: session=abc\r\n"));
+        assert!(received[0].ends_with("\r\n\r\nlevel=1&answer=a%20b%26c"));
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let response = parse_response(
+            b"HTTP/1.1 404 Not Found\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n2\r\nde\r\n0\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(response.status, 404);
+        assert_eq!(response.body, "abcde");
+
+        assert!(parse_response(b"garbage").is_err());
+        assert!(StdHttp.send(&Request::new(Method::Get, "ftp://x")).is_err());
+    }
+}
@@ -0,0 +1,255 @@
+use std::env;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Overrides the directory puzzle inputs are read from.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+/// Selects a per-user subdirectory of the input directory.
+pub const USER_VAR: &str = "AOC_USER";
+/// Name of the input read from `<dir>/day_XX.txt` itself, as opposed to a user's input.
+pub const DEFAULT_INPUT: &str = "default";
+
+#[derive(Debug)]
+pub struct InputError {
+    pub day: u8,
+    pub path: PathBuf,
+    pub source: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.source.kind() == io::ErrorKind::NotFound {
+            write!(
+                f,
+                "no input for day {} at `{}` (set {} or {} to look elsewhere)",
+                self.day,
+                self.path.display(),
+                INPUT_DIR_VAR,
+                USER_VAR
+            )
+        } else {
+            write!(
+                f,
+                "failed to read input for day {} from `{}`: {}",
+                self.day,
+                self.path.display(),
+                self.source
+            )
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Finds puzzle inputs at runtime: `<dir>/day_XX.txt`, or for a user either
+/// `<dir>/<user>/day_XX.txt` or `<dir>/day_XX/<user>.txt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputLoader {
+    dir: PathBuf,
+    user: Option<String>,
+}
+
+impl InputLoader {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            user: None,
+        }
+    }
+
+    /// The crate's `input` directory unless `AOC_INPUT_DIR` or `AOC_USER` say otherwise.
+    pub fn from_env() -> Self {
+        let dir = env::var_os(INPUT_DIR_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(default_dir);
+        let loader = Self::new(dir);
+        match env::var(USER_VAR) {
+            Ok(user) if !user.is_empty() => loader.with_user(user),
+            _ => loader,
+        }
+    }
+
+    pub fn with_user(mut self, user: impl Into<String>) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    /// The user, or `default` when reading from the input directory itself.
+    pub fn input_name(&self) -> &str {
+        self.user().unwrap_or(DEFAULT_INPUT)
+    }
+
+    /// A loader for the named input, as returned by `input_name`.
+    pub fn named(&self, name: &str) -> Self {
+        let loader = Self::new(self.dir.clone());
+        if name == DEFAULT_INPUT {
+            loader
+        } else {
+            loader.with_user(name)
+        }
+    }
+
+    /// Names of every input present for `day`: `default` and each user that has one.
+    pub fn discover(&self, day: u8) -> Vec<String> {
+        let mut names = Vec::new();
+        if self.named(DEFAULT_INPUT).path(day).is_file() {
+            names.push(DEFAULT_INPUT.to_string());
+        }
+
+        let mut users = std::fs::read_dir(&self.dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().join(file_name(day)).is_file())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect::<Vec<_>>();
+        for (name, _) in inputs_in(&self.dir.join(day_dir_name(day))) {
+            if !users.contains(&name) {
+                users.push(name);
+            }
+        }
+        users.sort();
+        names.extend(users);
+        names
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        let mut path = self.dir.clone();
+        if let Some(user) = &self.user {
+            let file = path.join(day_dir_name(day)).join(format!("{}.txt", user));
+            if file.is_file() {
+                return file;
+            }
+            path.push(user);
+        }
+        path.push(file_name(day));
+        path
+    }
+
+    pub fn load(&self, day: u8) -> Result<String, InputError> {
+        let path = self.path(day);
+        std::fs::read_to_string(&path).map_err(|source| InputError { day, path, source })
+    }
+}
+
+impl Default for InputLoader {
+    fn default() -> Self {
+        Self::new(default_dir())
+    }
+}
+
+pub fn file_name(day: u8) -> String {
+    format!("day_{:02}.txt", day)
+}
+
+fn day_dir_name(day: u8) -> String {
+    format!("day_{:02}", day)
+}
+
+/// Every `<name>.txt` file directly inside `dir`, sorted by name; a missing directory has none.
+pub fn inputs_in(dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut inputs = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "txt"))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_str()?.to_string();
+            Some((name, path))
+        })
+        .collect::<Vec<_>>();
+    inputs.sort();
+    inputs
+}
+
+fn default_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("input")
+}
+
+/// Loads a day's input using the environment's overrides.
+pub fn load(day: u8) -> Result<String, InputError> {
+    InputLoader::from_env().load(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path() {
+        let loader = InputLoader::new("inputs");
+        assert_eq!(loader.path(4), Path::new("inputs/day_04.txt"));
+
+        let loader = loader.with_user("alice");
+        assert_eq!(loader.path(12), Path::new("inputs/alice/day_12.txt"));
+    }
+
+    #[test]
+    fn test_named() {
+        let loader = InputLoader::new("inputs").with_user("alice");
+        assert_eq!(loader.input_name(), "alice");
+        assert_eq!(loader.named(DEFAULT_INPUT), InputLoader::new("inputs"));
+        assert_eq!(
+            loader.named("bob").path(1),
+            Path::new("inputs/bob/day_01.txt")
+        );
+    }
+
+    #[test]
+    fn test_discover() {
+        let dir = env::temp_dir().join(format!("aoc-discover-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("bob")).unwrap();
+        std::fs::create_dir_all(dir.join("alice")).unwrap();
+        std::fs::create_dir_all(dir.join("carol")).unwrap();
+        std::fs::write(dir.join("day_04.txt"), "").unwrap();
+        std::fs::write(dir.join("bob/day_04.txt"), "").unwrap();
+        std::fs::write(dir.join("alice/day_04.txt"), "").unwrap();
+        std::fs::write(dir.join("carol/day_05.txt"), "").unwrap();
+        std::fs::create_dir_all(dir.join("day_04")).unwrap();
+        std::fs::write(dir.join("day_04/dave.txt"), "").unwrap();
+        std::fs::write(dir.join("day_04/bob.txt"), "").unwrap();
+        std::fs::write(dir.join("day_04/notes.md"), "").unwrap();
+
+        let loader = InputLoader::new(&dir);
+        assert_eq!(loader.discover(4), vec!["default", "alice", "bob", "dave"]);
+        assert_eq!(loader.discover(5), vec!["carol"]);
+        assert!(loader.discover(6).is_empty());
+        assert_eq!(loader.named("dave").path(4), dir.join("day_04/dave.txt"));
+        assert_eq!(loader.named("bob").path(4), dir.join("day_04/bob.txt"));
+        assert_eq!(loader.named("bob").path(5), dir.join("bob/day_05.txt"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_load() {
+        let input = InputLoader::default().load(1).unwrap();
+        assert_eq!(input.lines().next(), Some("182"));
+    }
+
+    #[test]
+    fn test_missing_input() {
+        let error = InputLoader::default()
+            .with_user("nobody")
+            .load(25)
+            .unwrap_err();
+        assert_eq!(error.day, 25);
+        assert_eq!(error.source.kind(), io::ErrorKind::NotFound);
+        assert!(error.to_string().starts_with("no input for day 25 at `"));
+        assert!(error.to_string().contains("nobody"));
+    }
+}
@@ -1,5 +1,193 @@
+//! Solutions to Advent of Code 2021 and the tooling to run them.
+//!
+//! With the default `std` feature disabled only the solvers are built, as `no_std` code
+//! needing nothing but `alloc`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use core::any::Any;
+use core::fmt::Debug;
+
+use crate::prelude::*;
+
+#[cfg(feature = "std")]
+pub mod alloc_stats;
+pub mod answer;
+#[cfg(feature = "std")]
+pub mod bench;
+#[cfg(feature = "std")]
+pub mod client;
+pub mod collections;
+#[cfg(feature = "std")]
+pub mod compare;
+#[cfg(feature = "std")]
+pub mod config;
 pub mod day_01;
 pub mod day_02;
 pub mod day_03;
 pub mod day_04;
 pub mod day_05;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod examples;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod fuzz;
+#[cfg(feature = "std")]
+pub mod generate;
+#[cfg(feature = "std")]
+pub mod http;
+#[cfg(feature = "std")]
+pub mod input;
+#[cfg(feature = "std")]
+pub mod manifest;
+pub mod normalize;
+#[cfg(feature = "std")]
+pub mod oracle;
+#[cfg(feature = "std")]
+pub mod panic_hook;
+#[cfg(feature = "std")]
+pub mod parallel;
+pub mod parse;
+#[cfg(feature = "std")]
+pub mod repl;
+#[cfg(feature = "std")]
+pub mod report;
+#[cfg(feature = "std")]
+pub mod runner;
+#[cfg(feature = "std")]
+pub mod scaffold;
+#[cfg(feature = "std")]
+pub mod submit;
+pub mod trace;
+#[cfg(feature = "std")]
+pub mod verify;
+#[cfg(feature = "std")]
+pub mod watch;
+
+/// The parts of the standard prelude that live in `alloc`, so modules read the same with
+/// and without `std`.
+pub(crate) mod prelude {
+    pub use alloc::boxed::Box;
+    pub use alloc::format;
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec;
+    pub use alloc::vec::Vec;
+}
+
+pub use answer::Answer;
+pub use error::{ParseError, ParseErrorKind, Result};
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: alloc_stats::CountingAlloc = alloc_stats::CountingAlloc;
+
+/// A single day's puzzle: how to parse its input and solve both parts.
+pub trait Solution {
+    const DAY: u8;
+    const TITLE: &'static str;
+
+    type Input;
+    type Answer: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Self::Answer;
+    fn part2(input: &Self::Input) -> Self::Answer;
+}
+
+/// A day's parsed input with its type erased; it can be shared by threads solving each part.
+pub type Parsed = Box<dyn Any + Send + Sync>;
+
+/// Object safe view of a `Solution`, used by the registry so days can be iterated over.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+    fn parse(&self, input: &str) -> Result<Parsed>;
+    fn part1(&self, parsed: &dyn Any) -> Answer;
+    fn part2(&self, parsed: &dyn Any) -> Answer;
+    fn show(&self, parsed: &dyn Any) -> String;
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync,
+    S::Input: Debug + Send + Sync + 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed> {
+        Ok(Box::new(normalize::parse::<S>(input)?))
+    }
+
+    fn part1(&self, parsed: &dyn Any) -> Answer {
+        S::part1(downcast::<S>(parsed)).into()
+    }
+
+    fn part2(&self, parsed: &dyn Any) -> Answer {
+        S::part2(downcast::<S>(parsed)).into()
+    }
+
+    fn show(&self, parsed: &dyn Any) -> String {
+        format!("{:#?}", downcast::<S>(parsed))
+    }
+}
+
+pub(crate) fn downcast<S: Solution>(parsed: &dyn Any) -> &S::Input
+where
+    S::Input: 'static,
+{
+    parsed
+        .downcast_ref::<S::Input>()
+        .expect("parsed input belongs to another day")
+}
+
+/// Every implemented day, in order.
+pub static SOLUTIONS: &[&dyn DynSolution] = &[
+    &day_01::Day01,
+    &day_02::Day02,
+    &day_03::Day03,
+    &day_04::Day04,
+    &day_05::Day05,
+];
+
+pub fn solution(day: u8) -> Option<&'static dyn DynSolution> {
+    SOLUTIONS.iter().copied().find(|s| s.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_in_order() {
+        let days = SOLUTIONS.iter().map(|s| s.day()).collect::<Vec<_>>();
+        assert!(days.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(days[..5], [1, 2, 3, 4, 5]);
+        assert_eq!(solution(4).unwrap().title(), "Giant Squid");
+    }
+
+    #[test]
+    fn test_dyn_parse_error() {
+        let error = solution(1).unwrap().parse("199\n2x0").unwrap_err();
+        assert_eq!((error.day, error.line, error.column), (1, 2, 1));
+    }
+
+    #[test]
+    fn test_dyn_solution() {
+        let day = solution(1).unwrap();
+        let parsed = day
+            .parse("199\n200\n208\n210\n200\n207\n240\n269\n260\n263")
+            .unwrap();
+        assert_eq!(day.part1(parsed.as_ref()), 7u32);
+        assert_eq!(day.part2(parsed.as_ref()), 5u32);
+    }
+}
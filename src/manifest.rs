@@ -0,0 +1,279 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::Answer;
+
+#[derive(Debug)]
+pub enum ManifestError {
+    Io(PathBuf, io::Error),
+    Syntax { line: usize, message: String },
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManifestError::Io(path, e) => write!(f, "failed to access `{}`: {}", path.display(), e),
+            ManifestError::Syntax { line, message } => {
+                write!(f, "answer manifest line {}: {}", line, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+/// Expected answers keyed by day, input name and part, stored as `answers.toml`:
+///
+/// ```toml
+/// [day_01.default]
+/// part1 = 1215
+/// part2 = 1150
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifest {
+    answers: BTreeMap<(u8, String), [Option<Answer>; 2]>,
+}
+
+impl Manifest {
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml")
+    }
+
+    /// Reads a manifest, treating a missing file as an empty one.
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => text.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(ManifestError::Io(path.to_path_buf(), e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ManifestError> {
+        std::fs::write(path, self.to_string()).map_err(|e| ManifestError::Io(path.to_path_buf(), e))
+    }
+
+    pub fn expected(&self, day: u8, input: &str, part: u8) -> Option<&Answer> {
+        self.answers
+            .get(&(day, input.to_string()))
+            .and_then(|parts| parts[part_index(part)].as_ref())
+    }
+
+    pub fn record(&mut self, day: u8, input: &str, part: u8, answer: Answer) {
+        let parts = self.answers.entry((day, input.to_string())).or_default();
+        parts[part_index(part)] = Some(answer);
+    }
+
+    /// Every `(day, input)` pair with at least one expected answer, in order.
+    pub fn inputs(&self) -> impl Iterator<Item = (u8, &str)> {
+        self.answers
+            .keys()
+            .map(|(day, input)| (*day, input.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+fn part_index(part: u8) -> usize {
+    assert!(part == 1 || part == 2, "part must be 1 or 2, got {}", part);
+    usize::from(part - 1)
+}
+
+impl std::str::FromStr for Manifest {
+    type Err = ManifestError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut manifest = Manifest::default();
+        let mut table = None;
+
+        for (idx, line) in text.lines().enumerate() {
+            let syntax = |message: String| ManifestError::Syntax {
+                line: idx + 1,
+                message,
+            };
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let header = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| syntax("unclosed table header".to_string()))?;
+                let (day, input) = header.split_once('.').ok_or_else(|| {
+                    syntax(format!("expected `[day_XX.<input>]`, found `[{}]`", header))
+                })?;
+                let day = day
+                    .trim()
+                    .strip_prefix("day_")
+                    .and_then(|day| day.parse::<u8>().ok())
+                    .ok_or_else(|| syntax(format!("invalid day `{}`", day.trim())))?;
+                let input = unquote(input.trim())
+                    .ok_or_else(|| syntax(format!("invalid input name `{}`", input.trim())))?;
+                table = Some((day, input));
+                continue;
+            }
+
+            let (day, input) = table.as_ref().ok_or_else(|| {
+                syntax("answer outside of a `[day_XX.<input>]` table".to_string())
+            })?;
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| syntax(format!("expected `partN = <answer>`, found `{}`", line)))?;
+            let part = match key.trim() {
+                "part1" => 1,
+                "part2" => 2,
+                key => return Err(syntax(format!("unknown key `{}`", key))),
+            };
+            let value = value.trim();
+            let answer = if value.starts_with('"') {
+                Answer::Text(
+                    unquote(value).ok_or_else(|| syntax(format!("invalid string {}", value)))?,
+                )
+            } else if !value.is_empty() && value.chars().all(|c| c == '-' || c.is_ascii_digit()) {
+                value.parse().unwrap()
+            } else {
+                return Err(syntax(format!("invalid answer `{}`", value)));
+            };
+            manifest.record(*day, input, part, answer);
+        }
+
+        Ok(manifest)
+    }
+}
+
+impl fmt::Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# Expected answers, keyed by day and input name.")?;
+        writeln!(f, "# Regenerate with `aoc verify --bless`.")?;
+        for ((day, input), parts) in &self.answers {
+            writeln!(f)?;
+            writeln!(f, "[day_{:02}.{}]", day, key(input))?;
+            for (idx, answer) in parts.iter().enumerate() {
+                match answer {
+                    Some(Answer::Text(text)) => writeln!(f, "part{} = {}", idx + 1, quote(text))?,
+                    Some(answer) => writeln!(f, "part{} = {}", idx + 1, answer)?,
+                    None => {}
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn key(name: &str) -> String {
+    let bare = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        name.to_string()
+    } else {
+        quote(name)
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// A bare key, or a basic string with `\"` and `\\` escapes.
+pub(crate) fn unquote(text: &str) -> Option<String> {
+    let Some(inner) = text.strip_prefix('"') else {
+        let bare = !text.is_empty()
+            && text
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        return bare.then(|| text.to_string());
+    };
+
+    let mut result = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return chars.as_str().is_empty().then_some(result),
+            '\\' => match chars.next()? {
+                c @ ('"' | '\\') => result.push(c),
+                _ => return None,
+            },
+            c => result.push(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::DEFAULT_INPUT;
+
+    const MANIFEST: &str = r#"# answers
+[day_01.default]
+part1 = 1215
+part2 = 1150
+
+[day_02."bob smith"]
+part2 = -5
+
+[day_13.alice]
+part2 = "EFJKZLBL"
+"#;
+
+    #[test]
+    fn test_parse() {
+        let manifest = MANIFEST.parse::<Manifest>().unwrap();
+        assert_eq!(
+            manifest.expected(1, DEFAULT_INPUT, 1),
+            Some(&Answer::from(1215u32))
+        );
+        assert_eq!(
+            manifest.expected(1, DEFAULT_INPUT, 2),
+            Some(&Answer::from(1150u32))
+        );
+        assert_eq!(manifest.expected(2, "bob smith", 1), None);
+        assert_eq!(
+            manifest.expected(2, "bob smith", 2),
+            Some(&Answer::from(-5i32))
+        );
+        assert_eq!(
+            manifest.expected(13, "alice", 2),
+            Some(&Answer::from("EFJKZLBL"))
+        );
+        assert_eq!(
+            manifest.inputs().collect::<Vec<_>>(),
+            vec![(1, "default"), (2, "bob smith"), (13, "alice")]
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let manifest = MANIFEST.parse::<Manifest>().unwrap();
+        let text = manifest.to_string();
+        assert!(text.contains("[day_02.\"bob smith\"]\npart2 = -5\n"));
+        assert_eq!(text.parse::<Manifest>().unwrap(), manifest);
+    }
+
+    #[test]
+    fn test_syntax_error() {
+        let error = "[day_01.default]\npart3 = 1"
+            .parse::<Manifest>()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "answer manifest line 2: unknown key `part3`"
+        );
+
+        assert!("part1 = 1".parse::<Manifest>().is_err());
+        assert!("[day_x.default]".parse::<Manifest>().is_err());
+        assert!("[day_01.default]\npart1 = 12 34"
+            .parse::<Manifest>()
+            .is_err());
+        assert!("[day_01.default]\npart1 = \"abc"
+            .parse::<Manifest>()
+            .is_err());
+    }
+}
@@ -0,0 +1,211 @@
+//! The cleanup every input gets before a solver sees it, so parsers only handle one layout.
+//!
+//! - a UTF-8 byte order mark is dropped
+//! - `\r\n` line endings become `\n`, and there is none after the last line
+//! - indentation and trailing whitespace are trimmed from every line
+//! - blank lines at the start and end are dropped, and runs of them become one
+//!
+//! Parse errors are reported against the original input's lines and columns.
+
+use crate::prelude::*;
+use crate::{ParseError, Result, Solution};
+
+const BOM: char = '\u{feff}';
+
+/// Parses `input` with `S` once it has been normalized.
+pub fn parse<S: Solution>(input: &str) -> Result<S::Input> {
+    let normalized = Normalized::new(input);
+    S::parse(&normalized.text).map_err(|error| normalized.relocate(error))
+}
+
+/// A normalized input, remembering which original line each of its lines came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Normalized<'a> {
+    pub text: String,
+    origins: Vec<Origin<'a>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Origin<'a> {
+    idx: usize,
+    /// How many characters of indentation were trimmed.
+    indent: usize,
+    /// The original line, without its line ending and byte order mark.
+    source: &'a str,
+}
+
+impl<'a> Normalized<'a> {
+    pub fn new(input: &'a str) -> Self {
+        let mut lines = Lines::default();
+        let mut origins = Vec::new();
+        for (idx, raw) in input.lines().enumerate() {
+            for origin in lines.push(idx, raw) {
+                origins.push(origin);
+            }
+        }
+
+        let text = origins
+            .iter()
+            .map(Origin::trimmed)
+            .collect::<Vec<_>>()
+            .join("\n");
+        Self { text, origins }
+    }
+
+    /// Points an error in the normalized text back at the original input.
+    pub fn relocate(&self, error: ParseError) -> ParseError {
+        match self.origins.get(error.line.wrapping_sub(1)) {
+            Some(origin) => origin.relocate(error),
+            None => error,
+        }
+    }
+}
+
+impl<'a> Origin<'a> {
+    fn new(idx: usize, raw: &'a str) -> Self {
+        let source = raw.strip_suffix('\r').unwrap_or(raw);
+        let source = match idx {
+            0 => source.strip_prefix(BOM).unwrap_or(source),
+            _ => source,
+        };
+        let indent = source.chars().take_while(|c| c.is_whitespace()).count();
+        Self {
+            idx,
+            indent,
+            source,
+        }
+    }
+
+    fn trimmed(&self) -> &'a str {
+        self.source.trim()
+    }
+
+    fn relocate(&self, mut error: ParseError) -> ParseError {
+        error.line = self.idx + 1;
+        error.column += self.indent;
+        error.source_line = self.source.to_string();
+        error
+    }
+}
+
+/// Applies the blank line rules one line at a time, so streamed input can follow them too.
+#[derive(Debug, Default)]
+struct Lines {
+    /// Whether a line has been passed on yet; blank lines before the first are dropped.
+    started: bool,
+    /// The index of the first of the blank lines seen since the last line passed on.
+    blank: Option<usize>,
+}
+
+impl Lines {
+    /// The lines to pass on after line `idx`: none while inside a run of blank lines, or the
+    /// run's first line followed by this one.
+    fn push<'a>(&mut self, idx: usize, raw: &'a str) -> impl Iterator<Item = Origin<'a>> {
+        let origin = Origin::new(idx, raw);
+        if origin.trimmed().is_empty() {
+            self.blank = self.blank.or(Some(idx));
+            return None.into_iter().chain(None);
+        }
+
+        let blank = self.blank.take().filter(|_| self.started);
+        self.started = true;
+        let blank = blank.map(|idx| Origin {
+            idx,
+            indent: 0,
+            source: "",
+        });
+        blank.into_iter().chain(Some(origin))
+    }
+}
+
+/// Calls `f` with the index and text of every normalized line of a stream, reusing one
+/// buffer so memory use does not grow with the input. Returns how many lines there were.
+#[cfg(feature = "std")]
+pub(crate) fn for_each_line(
+    mut reader: impl std::io::BufRead,
+    mut f: impl FnMut(usize, &str) -> Result<()>,
+) -> core::result::Result<usize, crate::error::ReadError> {
+    let mut buffer = String::new();
+    let mut lines = Lines::default();
+    let mut idx = 0;
+    let mut count = 0;
+    loop {
+        buffer.clear();
+        if reader.read_line(&mut buffer)? == 0 {
+            return Ok(count);
+        }
+        let raw = buffer.strip_suffix('\n').unwrap_or(&buffer);
+        for origin in lines.push(idx, raw) {
+            f(origin.idx, origin.trimmed()).map_err(|error| origin.relocate(error))?;
+            count += 1;
+        }
+        idx += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_02::Day02;
+
+    #[test]
+    fn test_normalize() {
+        let input = "\u{feff}\r\n\r\n  forward 5  \r\nup 2\r\n\r\n\r\n\tdown 1\r\n  \r\n\r\n";
+        assert_eq!(Normalized::new(input).text, "forward 5\nup 2\n\ndown 1");
+        assert_eq!(Normalized::new("").text, "");
+        assert_eq!(Normalized::new(" \n\t\n").text, "");
+        assert_eq!(Normalized::new("a\nb\n").text, "a\nb");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_every_day() {
+        for day in crate::SOLUTIONS {
+            let sample = crate::examples::input(day.day(), "sample");
+            let mangled = sample
+                .lines()
+                .map(|line| match line {
+                    "" => "\r\n \r\n".to_string(),
+                    _ => format!("  {}\t\r\n", line),
+                })
+                .collect::<String>();
+            let mangled = format!("\u{feff}\r\n{}\r\n\r\n", mangled);
+
+            let expected = day.parse(&sample).unwrap();
+            let parsed = day.parse(&mangled).unwrap();
+            assert_eq!(day.show(parsed.as_ref()), day.show(expected.as_ref()));
+        }
+    }
+
+    #[test]
+    fn test_relocate() {
+        let error = parse::<Day02>("\u{feff}\r\nforward 5\r\n   up x \r\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 7));
+        assert_eq!(error.source_line, "   up x ");
+        assert_eq!(error.text, "x");
+
+        let error = parse::<Day02>("forward 5\n\n\nup 1\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_for_each_line() {
+        let input = "\u{feff}\n  1\r\n\n\n2 \n\n";
+        let mut lines = Vec::new();
+        let count = for_each_line(input.as_bytes(), |idx, line| {
+            lines.push((idx, line.to_string()));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(
+            lines,
+            [
+                (1, "1".to_string()),
+                (2, String::new()),
+                (4, "2".to_string())
+            ]
+        );
+    }
+}
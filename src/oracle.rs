@@ -0,0 +1,254 @@
+//! Naive reference solutions, written straight from the puzzle text with no attention to
+//! speed, to check the real solvers against on inputs from `generate`.
+//!
+//! They expect valid inputs and panic on anything else.
+
+use std::collections::HashMap;
+
+use crate::Answer;
+
+/// The answer to `part` of `day`, or `None` if there is no oracle for the day.
+pub fn solve(day: u8, part: u8, input: &str) -> Option<Answer> {
+    match (day, part) {
+        (1, _) => Some(depths(input, part).into()),
+        (2, _) => Some(commands(input, part).into()),
+        (3, 1) => Some(power_consumption(input).into()),
+        (3, _) => Some(life_support_rating(input).into()),
+        (4, _) => Some(bingo(input, part).into()),
+        (5, _) => Some(vents(input, part).into()),
+        _ => None,
+    }
+}
+
+fn depths(input: &str, part: u8) -> usize {
+    let depths = input
+        .lines()
+        .map(|line| line.parse::<u64>().unwrap())
+        .collect::<Vec<_>>();
+    let size = if part == 1 { 1 } else { 3 };
+    let sums = depths
+        .windows(size)
+        .map(|window| window.iter().sum())
+        .collect::<Vec<u64>>();
+    sums.windows(2).filter(|pair| pair[1] > pair[0]).count()
+}
+
+fn commands(input: &str, part: u8) -> i64 {
+    let (mut horizontal, mut depth, mut aim) = (0i64, 0i64, 0i64);
+    for line in input.lines() {
+        let (command, value) = line.split_once(' ').unwrap();
+        let value = value.parse::<i64>().unwrap();
+        if part == 1 {
+            match command {
+                "forward" => horizontal += value,
+                "down" => depth += value,
+                "up" => depth -= value,
+                _ => panic!("unknown command `{}`", command),
+            }
+        } else {
+            match command {
+                "forward" => {
+                    horizontal += value;
+                    depth += aim * value;
+                }
+                "down" => aim += value,
+                "up" => aim -= value,
+                _ => panic!("unknown command `{}`", command),
+            }
+        }
+    }
+    horizontal * depth
+}
+
+/// How many of `numbers` have a `1` at `position`, and how many a `0`.
+fn count_bits(numbers: &[&str], position: usize) -> (usize, usize) {
+    let ones = numbers
+        .iter()
+        .filter(|number| number.as_bytes()[position] == b'1')
+        .count();
+    (ones, numbers.len() - ones)
+}
+
+fn power_consumption(input: &str) -> u64 {
+    let numbers = input.lines().collect::<Vec<_>>();
+    let (mut gamma, mut epsilon) = (0, 0);
+    for position in 0..numbers[0].len() {
+        let (ones, zeros) = count_bits(&numbers, position);
+        gamma = gamma * 2 + u64::from(ones >= zeros);
+        epsilon = epsilon * 2 + u64::from(ones < zeros);
+    }
+    gamma * epsilon
+}
+
+/// A rating the bit criteria cannot narrow down to one number, by filtering out every number
+/// or leaving repeats of one, counts as 0, as the puzzle leaves it undefined.
+fn life_support_rating(input: &str) -> u64 {
+    let rating = |keep_ones: fn(usize, usize) -> bool| {
+        let mut numbers = input.lines().collect::<Vec<_>>();
+        let width = numbers[0].len();
+        let mut position = 0;
+        while numbers.len() > 1 && position < width {
+            let (ones, zeros) = count_bits(&numbers, position);
+            let bit = if keep_ones(ones, zeros) { b'1' } else { b'0' };
+            numbers.retain(|number| number.as_bytes()[position] == bit);
+            position += 1;
+        }
+        match numbers[..] {
+            [number] => u64::from_str_radix(number, 2).unwrap(),
+            _ => 0,
+        }
+    };
+    rating(|ones, zeros| ones >= zeros) * rating(|ones, zeros| ones < zeros)
+}
+
+fn bingo(input: &str, part: u8) -> u64 {
+    let mut lines = input.lines();
+    let draws = lines
+        .next()
+        .unwrap()
+        .split(',')
+        .map(|n| n.parse::<u64>().unwrap())
+        .collect::<Vec<_>>();
+    let boards = lines
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            line.split_whitespace()
+                .map(|n| n.parse::<u64>().unwrap())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let boards = boards.chunks(5).collect::<Vec<_>>();
+
+    // the number of draws after which each board has a full row or column
+    let turn = |board: &[Vec<u64>]| {
+        let completed_at = |line: Vec<u64>| {
+            let drawn_at = line
+                .iter()
+                .map(|n| draws.iter().position(|d| d == n))
+                .collect::<Option<Vec<_>>>()?;
+            drawn_at.into_iter().max().map(|last| last + 1)
+        };
+        let rows = board.iter().cloned();
+        let columns = (0..5).map(|x| board.iter().map(|row| row[x]).collect());
+        rows.chain(columns).filter_map(completed_at).min()
+    };
+    let turns = boards.iter().map(|board| turn(board)).collect::<Vec<_>>();
+
+    // ties go to the board checked first when it wins, and last when it is the last to win
+    let winner = if part == 1 {
+        (0..boards.len())
+            .filter(|&i| turns[i].is_some())
+            .min_by_key(|&i| (turns[i], i))
+    } else if turns.iter().all(Option::is_some) {
+        (0..boards.len()).max_by_key(|&i| (turns[i], i))
+    } else {
+        None
+    };
+    let Some(winner) = winner else {
+        return 0;
+    };
+
+    let turn = turns[winner].unwrap();
+    let unmarked = boards[winner]
+        .iter()
+        .flatten()
+        .filter(|n| !draws[..turn].contains(n))
+        .sum::<u64>();
+    unmarked * draws[turn - 1]
+}
+
+fn vents(input: &str, part: u8) -> usize {
+    let mut covered = HashMap::new();
+    for line in input.lines() {
+        let points = line
+            .split(" -> ")
+            .flat_map(|point| point.split(','))
+            .map(|n| n.parse::<i32>().unwrap())
+            .collect::<Vec<_>>();
+        let [x1, y1, x2, y2] = points[..] else {
+            panic!("invalid line `{}`", line);
+        };
+        let (dx, dy) = (x2 - x1, y2 - y1);
+        let straight = dx == 0 || dy == 0;
+        let diagonal = dx.abs() == dy.abs();
+        if !(straight || part == 2 && diagonal) {
+            continue;
+        }
+        for i in 0..=dx.abs().max(dy.abs()) {
+            let point = (x1 + i * dx.signum(), y1 + i * dy.signum());
+            *covered.entry(point).or_insert(0) += 1;
+        }
+    }
+    covered.values().filter(|&&count| count >= 2).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::{self, Rng};
+    use crate::{examples, SOLUTIONS};
+
+    const SEEDS: u64 = 2000;
+
+    #[test]
+    fn test_examples() {
+        for day in SOLUTIONS {
+            for example in examples::load(day.day()).unwrap() {
+                for part in [1, 2] {
+                    if let Some(expected) = example.expected(part) {
+                        let answer = solve(day.day(), part, &example.input);
+                        assert_eq!(answer.as_ref(), Some(expected), "day {}", day.day());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_against_solvers() {
+        for day in SOLUTIONS {
+            // days scaffolded since have no generator or oracle yet
+            if generate::input(day.day(), &mut Rng::new(0)).is_none() {
+                continue;
+            }
+            let mut compared = 0;
+            for seed in 0..SEEDS {
+                let input = generate::input(day.day(), &mut Rng::new(seed)).unwrap();
+                let parsed = day.parse(&input).unwrap();
+                for part in [1, 2] {
+                    let Some(expected) = solve(day.day(), part, &input) else {
+                        continue;
+                    };
+                    let answer = match part {
+                        1 => day.part1(parsed.as_ref()),
+                        _ => day.part2(parsed.as_ref()),
+                    };
+                    assert_eq!(
+                        answer,
+                        expected,
+                        "day {} part {} seed {}:\n{}",
+                        day.day(),
+                        part,
+                        seed,
+                        input
+                    );
+                    compared += 1;
+                }
+            }
+            // most inputs must have a defined answer for the comparison to mean anything
+            assert!(compared > SEEDS, "day {}: {} compared", day.day(), compared);
+        }
+    }
+
+    #[test]
+    fn test_undefined_rating() {
+        // the comparison above must cover ratings the criteria cannot narrow to one number
+        let undefined = (0..SEEDS)
+            .map(|seed| generate::diagnostic(&mut Rng::new(seed)))
+            .filter(|input| life_support_rating(input) == 0)
+            .count();
+        assert!(undefined > 0);
+        assert_eq!(life_support_rating("101\n101\n"), 0);
+        assert_eq!(life_support_rating("110\n111\n"), 0);
+    }
+}
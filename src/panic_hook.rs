@@ -0,0 +1,59 @@
+//! A panic hook for code that reports panics itself, such as the fuzzer and `aoc compare`:
+//! panics on the threads that ask for it are recorded instead of printed.
+
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+thread_local! {
+    /// Whether panics on this thread are being recorded rather than printed.
+    static RECORDING: Cell<bool> = const { Cell::new(false) };
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Runs `f` with panics on this thread recorded for `take_last` instead of printed.
+///
+/// The hook is installed once and for good, passing other threads' panics on to the previous
+/// hook, since swapping hooks back and forth races with any other thread doing the same.
+pub fn with_panic_hook<T>(f: impl FnOnce() -> T) -> T {
+    INSTALL_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if RECORDING.with(Cell::get) {
+                let message = info.to_string().replace('\n', " ");
+                LAST_PANIC.with(|last| *last.borrow_mut() = Some(message));
+            } else {
+                previous(info);
+            }
+        }));
+    });
+    let recording = RECORDING.with(|recording| recording.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    RECORDING.with(|r| r.set(recording));
+    result.unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
+
+/// Where and why the last panic recorded on this thread happened, such as
+/// `panicked at src/day_05.rs:189:17: ...`, clearing it.
+pub fn take_last() -> Option<String> {
+    LAST_PANIC.with(|last| last.borrow_mut().take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_panic_hook() {
+        // another thread's panic still reaches the previous hook, not this thread's record
+        let _ = with_panic_hook(|| std::thread::spawn(|| panic!("elsewhere")).join());
+        assert_eq!(take_last(), None);
+
+        let _ = with_panic_hook(|| panic::catch_unwind(|| panic!("here")));
+        let recorded = take_last().unwrap();
+        assert!(recorded.contains("here"), "{}", recorded);
+        assert!(!RECORDING.with(Cell::get));
+    }
+}
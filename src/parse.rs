@@ -0,0 +1,317 @@
+//! Parsers for the shapes puzzle inputs come in, shared between days.
+//!
+//! Each takes the day and the line it parses, as `idx` and `line`, so that errors point at the
+//! offending text the same way wherever they come from.
+
+use core::str::FromStr;
+
+use crate::error::{ParseError, ParseErrorKind};
+use crate::prelude::*;
+use crate::Result;
+
+/// Parses `token` (a subslice of `line`) as a number.
+pub fn number<T: FromStr>(day: u8, idx: usize, line: &str, token: &str) -> Result<T> {
+    if token.is_empty() {
+        let kind = ParseErrorKind::Missing {
+            expected: "number".to_string(),
+        };
+        return Err(ParseError::at(day, kind, idx, line, token));
+    }
+
+    token
+        .parse()
+        .map_err(|_| ParseError::at(day, ParseErrorKind::InvalidNumber, idx, line, token))
+}
+
+/// Numbers separated by `separator`, such as `7,4,9,5`. Whitespace around each is ignored.
+pub fn list<T: FromStr>(day: u8, idx: usize, line: &str, separator: char) -> Result<Vec<T>> {
+    line.split(separator)
+        .map(|token| number(day, idx, line, token.trim()))
+        .collect()
+}
+
+/// Exactly `N` whitespace separated fields, named in `names` for when one is missing.
+pub fn fields<'a, const N: usize>(
+    day: u8,
+    idx: usize,
+    line: &'a str,
+    names: [&str; N],
+) -> Result<[&'a str; N]> {
+    let mut tokens = line.split_ascii_whitespace();
+    let mut fields = [""; N];
+    for (field, name) in fields.iter_mut().zip(names) {
+        *field = tokens
+            .next()
+            .ok_or_else(|| ParseError::end_of_line(day, name, idx, line))?;
+    }
+    match tokens.next() {
+        Some(extra) => Err(unexpected(day, "end of line", idx, line, extra)),
+        None => Ok(fields),
+    }
+}
+
+/// Matches `line` against `pattern`, in which each `{}` captures the text up to the literal
+/// text following it, or to the end of the line, such as `"{},{} -> {},{}"`.
+///
+/// Captures must be separated by some literal text, and `N` must be how many there are.
+pub fn pattern<'a, const N: usize>(
+    day: u8,
+    idx: usize,
+    line: &'a str,
+    pattern: &str,
+) -> Result<[&'a str; N]> {
+    let mut literals = pattern.split("{}");
+    let first = literals.next().unwrap_or_default();
+    let mut rest = line.strip_prefix(first).ok_or_else(|| {
+        let expected = format!("`{}`", first);
+        unexpected(day, &expected, idx, line, line)
+    })?;
+
+    let mut captures = [""; N];
+    let mut count = 0;
+    for literal in literals {
+        assert!(
+            count < N,
+            "pattern `{}` has more than {} captures",
+            pattern,
+            N
+        );
+        let capture = match literal {
+            "" => core::mem::take(&mut rest),
+            _ => {
+                let end = rest.find(literal).ok_or_else(|| {
+                    ParseError::end_of_line(day, &format!("`{}`", literal), idx, line)
+                })?;
+                let capture = &rest[..end];
+                rest = &rest[end + literal.len()..];
+                capture
+            }
+        };
+        captures[count] = capture;
+        count += 1;
+    }
+    assert_eq!(
+        count, N,
+        "pattern `{}` does not have {} captures",
+        pattern, N
+    );
+
+    match rest {
+        "" => Ok(captures),
+        extra => Err(unexpected(day, "end of line", idx, line, extra)),
+    }
+}
+
+/// A run of non-blank lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// The index of the block's first line in the input.
+    pub start: usize,
+    pub lines: Vec<&'a str>,
+}
+
+impl<'a> Block<'a> {
+    /// The block's lines, each with its index in the input.
+    pub fn indexed(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        (self.start..).zip(self.lines.iter().copied())
+    }
+
+    /// The index and text of the block's last line.
+    fn last(&self) -> (usize, &'a str) {
+        let last = self.lines.len() - 1;
+        (self.start + last, self.lines[last])
+    }
+}
+
+/// The blocks of `input` separated by blank lines, of which there may be any number.
+pub fn blocks(input: &str) -> Vec<Block<'_>> {
+    let mut blocks = Vec::<Block>::new();
+    let mut blank = true;
+    for (idx, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            blank = true;
+            continue;
+        }
+        match blocks.last_mut() {
+            Some(block) if !blank => block.lines.push(line),
+            _ => blocks.push(Block {
+                start: idx,
+                lines: vec![line],
+            }),
+        }
+        blank = false;
+    }
+    blocks
+}
+
+/// A block of exactly `H` rows of `W` whitespace separated numbers, such as a bingo board.
+pub fn grid<T, const W: usize, const H: usize>(day: u8, block: &Block) -> Result<[[T; W]; H]>
+where
+    T: FromStr + Copy + Default,
+{
+    let mut grid = [[T::default(); W]; H];
+    let mut lines = block.indexed();
+    for row in grid.iter_mut() {
+        let (idx, line) = lines.next().ok_or_else(|| {
+            let (idx, line) = block.last();
+            ParseError::end_of_line(day, "row", idx, line)
+        })?;
+        let fields = fields(day, idx, line, ["number"; W])?;
+        for (cell, token) in row.iter_mut().zip(fields) {
+            *cell = number(day, idx, line, token)?;
+        }
+    }
+    match lines.next() {
+        Some((idx, extra)) => Err(unexpected(day, "blank line", idx, extra, extra.trim())),
+        None => Ok(grid),
+    }
+}
+
+/// One row of a character grid, each character turned into a cell by `cell`, which returns
+/// `None` for characters other than those described by `expected`.
+///
+/// `width` is the width of the first row, which every other row must match.
+pub fn char_row<T>(
+    day: u8,
+    idx: usize,
+    line: &str,
+    width: &mut Option<usize>,
+    cell: impl Fn(char) -> Option<T>,
+    expected: &str,
+) -> Result<Vec<T>> {
+    let token = line.trim();
+    if token.is_empty() {
+        return Err(ParseError::end_of_line(day, expected, idx, line));
+    }
+    let len = token.chars().count();
+    match *width {
+        None => *width = Some(len),
+        Some(width) if width != len => {
+            let expected = format!("{} characters", width);
+            return Err(unexpected(day, &expected, idx, line, token));
+        }
+        _ => {}
+    }
+
+    token
+        .char_indices()
+        .map(|(offset, c)| {
+            cell(c).ok_or_else(|| {
+                let bad = &token[offset..offset + c.len_utf8()];
+                unexpected(day, expected, idx, line, bad)
+            })
+        })
+        .collect()
+}
+
+/// Every line of `input` as a row of cells, all as wide as the first; see `char_row`.
+pub fn char_grid<T>(
+    day: u8,
+    input: &str,
+    cell: impl Fn(char) -> Option<T>,
+    expected: &str,
+) -> Result<Vec<Vec<T>>> {
+    let mut width = None;
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| char_row(day, idx, line, &mut width, &cell, expected))
+        .collect()
+}
+
+fn unexpected(day: u8, expected: &str, idx: usize, line: &str, token: &str) -> ParseError {
+    let kind = ParseErrorKind::Unexpected {
+        expected: expected.to_string(),
+    };
+    ParseError::at(day, kind, idx, line, token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list() {
+        assert_eq!(list::<u32>(4, 0, "7,4, 9", ','), Ok(vec![7, 4, 9]));
+
+        let error = list::<u32>(4, 0, "7,,9", ',').unwrap_err();
+        assert_eq!(error.column, 3);
+        assert_eq!(error.to_string(), "missing number");
+    }
+
+    #[test]
+    fn test_fields() {
+        assert_eq!(
+            fields(2, 0, "up  3", ["command", "number"]),
+            Ok(["up", "3"])
+        );
+
+        let error = fields(2, 0, "up", ["command", "number"]).unwrap_err();
+        assert_eq!(error.to_string(), "missing number");
+
+        let error = fields(2, 0, "up 3 4", ["command", "number"]).unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (6, "4"));
+    }
+
+    #[test]
+    fn test_pattern() {
+        let line = "0,9 -> 5,9";
+        let parsed = pattern(5, 0, line, "{},{} -> {},{}");
+        assert_eq!(parsed, Ok(["0", "9", "5", "9"]));
+        assert_eq!(pattern(0, 0, "<1>", "<{}>"), Ok(["1"]));
+
+        let error = pattern::<4>(5, 0, "0,9 => 5,9", "{},{} -> {},{}").unwrap_err();
+        assert_eq!(error.column, 11);
+        assert_eq!(error.to_string(), "missing ` -> `");
+
+        let error = pattern::<1>(0, 0, "(1)", "<{}>").unwrap_err();
+        assert_eq!(error.to_string(), "expected `<`, found `(1)`");
+
+        let error = pattern::<1>(0, 0, "<1>>", "<{}>").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (4, ">"));
+    }
+
+    #[test]
+    fn test_blocks() {
+        let blocks = blocks("a\n\n\nb\nc\n \nd\n");
+        let starts = blocks.iter().map(|block| block.start).collect::<Vec<_>>();
+        assert_eq!(starts, [0, 3, 6]);
+        assert_eq!(blocks[1].lines, ["b", "c"]);
+        assert_eq!(blocks[1].indexed().last(), Some((4, "c")));
+        assert_eq!(super::blocks(""), []);
+    }
+
+    #[test]
+    fn test_grid() {
+        let block = &blocks("1 2\n 3  4\n")[0];
+        assert_eq!(grid::<u32, 2, 2>(0, block), Ok([[1, 2], [3, 4]]));
+
+        let error = grid::<u32, 2, 3>(0, block).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+        assert_eq!(error.to_string(), "missing row");
+
+        let error = grid::<u32, 2, 1>(0, block).unwrap_err();
+        assert_eq!(error.to_string(), "expected blank line, found `3  4`");
+
+        let error = grid::<u32, 3, 2>(0, block).unwrap_err();
+        assert_eq!(error.to_string(), "missing number");
+    }
+
+    #[test]
+    fn test_char_grid() {
+        let cell = |c| match c {
+            '.' => Some(false),
+            '#' => Some(true),
+            _ => None,
+        };
+        let grid = char_grid(0, "#.\n.#", cell, "`.` or `#`");
+        assert_eq!(grid, Ok(vec![vec![true, false], vec![false, true]]));
+
+        let error = char_grid(0, "#.\n.", cell, "`.` or `#`").unwrap_err();
+        assert_eq!(error.to_string(), "expected 2 characters, found `.`");
+
+        let error = char_grid(0, "#.\n.x", cell, "`.` or `#`").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.to_string(), "expected `.` or `#`, found `x`");
+    }
+}
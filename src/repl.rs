@@ -0,0 +1,303 @@
+use std::any::Any;
+use std::time::Instant;
+
+use crate::bench::format_duration;
+use crate::input::InputLoader;
+use crate::{day_01, day_02, day_03, day_04, day_05, examples};
+use crate::{downcast, solution, DynSolution, Parsed, Solution, SOLUTIONS};
+
+const HELP: &str = "\
+commands:
+  load <DAY> [INPUT]  parse an input, or an example by name, for DAY
+  inputs              list the inputs and examples of the loaded day
+  show                print the parsed input
+  part1, part2        solve a part of the loaded input
+  days                list the implemented days
+  help                show this message
+  quit                leave the REPL";
+
+/// Extra REPL commands a day offers for poking at its parsed input.
+pub trait Explore: Solution {
+    /// The commands, as `(usage, description)` pairs.
+    const COMMANDS: &'static [(&'static str, &'static str)];
+
+    /// Runs one of `COMMANDS`; `args` excludes the command name itself.
+    fn explore(input: &Self::Input, command: &str, args: &[&str]) -> Result<String, ExploreError>;
+}
+
+/// Why an `Explore` command gave no output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExploreError {
+    /// The command is not one of the day's, or its arguments do not fit its usage. The REPL
+    /// tells which, from `Explore::COMMANDS`.
+    Usage,
+    Failed(String),
+}
+
+impl From<String> for ExploreError {
+    fn from(message: String) -> Self {
+        ExploreError::Failed(message)
+    }
+}
+
+impl From<&str> for ExploreError {
+    fn from(message: &str) -> Self {
+        ExploreError::Failed(message.to_string())
+    }
+}
+
+/// Object safe view of an `Explore`, as `DynSolution` is of a `Solution`.
+trait DynExplore: Sync {
+    fn day(&self) -> u8;
+    fn commands(&self) -> &'static [(&'static str, &'static str)];
+    fn explore(&self, parsed: &dyn Any, command: &str, args: &[&str]) -> Result<String, String>;
+}
+
+impl<S> DynExplore for S
+where
+    S: Explore + Sync,
+    S::Input: 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        S::COMMANDS
+    }
+
+    fn explore(&self, parsed: &dyn Any, command: &str, args: &[&str]) -> Result<String, String> {
+        S::explore(downcast::<S>(parsed), command, args).map_err(|error| match error {
+            ExploreError::Usage => match S::COMMANDS
+                .iter()
+                .find(|(usage, _)| usage.split(' ').next() == Some(command))
+            {
+                Some((usage, _)) => format!("usage: {}", usage),
+                None => format!("unknown command `{}`", command),
+            },
+            ExploreError::Failed(message) => message,
+        })
+    }
+}
+
+/// Every day with commands of its own.
+static EXPLORERS: &[&dyn DynExplore] = &[
+    &day_01::Day01,
+    &day_02::Day02,
+    &day_03::Day03,
+    &day_04::Day04,
+    &day_05::Day05,
+];
+
+fn explorer(day: u8) -> Option<&'static dyn DynExplore> {
+    EXPLORERS.iter().copied().find(|e| e.day() == day)
+}
+
+/// A loaded day: its solution, the name of the input and the parsed form of it.
+struct Session {
+    solution: &'static dyn DynSolution,
+    input: String,
+    parsed: Parsed,
+}
+
+/// Line based interpreter behind `aoc repl`.
+///
+/// Commands the REPL does not know itself are handed to the loaded day's `Explore`, if it
+/// has one, so days can offer their own ways of poking at parsed state.
+pub struct Repl {
+    loader: InputLoader,
+    session: Option<Session>,
+}
+
+impl Repl {
+    pub fn new(loader: InputLoader) -> Self {
+        Self {
+            loader,
+            session: None,
+        }
+    }
+
+    pub fn prompt(&self) -> String {
+        match &self.session {
+            Some(session) => format!("day {:02} ({})> ", session.solution.day(), session.input),
+            None => "aoc> ".to_string(),
+        }
+    }
+
+    /// Runs one line of input, returning what to print.
+    pub fn eval(&mut self, line: &str) -> Result<String, String> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let (command, args) = match words.split_first() {
+            Some((command, args)) => (*command, args),
+            None => return Ok(String::new()),
+        };
+
+        match (command, args) {
+            ("help", []) => Ok(self.help()),
+            ("days", []) => Ok(SOLUTIONS
+                .iter()
+                .map(|s| format!("{:2}  {}", s.day(), s.title()))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            ("load", [day, rest @ ..]) if rest.len() <= 1 => {
+                let day = day
+                    .parse::<u8>()
+                    .map_err(|_| format!("invalid day `{}`", day))?;
+                let name = match rest.first() {
+                    Some(name) => name.to_string(),
+                    None => self.loader.input_name().to_string(),
+                };
+                self.load(day, &name)
+            }
+            ("load", _) => Err("usage: load <DAY> [INPUT]".to_string()),
+            _ => {
+                let session = self
+                    .session
+                    .as_ref()
+                    .ok_or("no day loaded, try `load <DAY>`")?;
+                session.eval(&self.loader, command, args)
+            }
+        }
+    }
+
+    fn help(&self) -> String {
+        let mut help = HELP.to_string();
+        if let Some(session) = &self.session {
+            help.push_str(&format!("\n\nday {:02}:", session.solution.day()));
+            let commands = explorer(session.solution.day()).map_or(&[][..], |e| e.commands());
+            for (usage, description) in commands {
+                help.push_str(&format!("\n  {:<18}  {}", usage, description));
+            }
+        }
+        help
+    }
+
+    fn load(&mut self, day: u8, name: &str) -> Result<String, String> {
+        let solution = solution(day).ok_or_else(|| format!("day {} is not implemented", day))?;
+        let text = if self.loader.discover(day).iter().any(|input| input == name) {
+            self.loader
+                .named(name)
+                .load(day)
+                .map_err(|e| e.to_string())?
+        } else {
+            examples::load(day)
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .find(|example| example.name == name)
+                .map(|example| example.input)
+                .ok_or_else(|| format!("day {} has no input or example `{}`", day, name))?
+        };
+
+        let start = Instant::now();
+        let parsed = solution.parse(&text).map_err(|e| e.render())?;
+        let elapsed = start.elapsed();
+        self.session = Some(Session {
+            solution,
+            input: name.to_string(),
+            parsed,
+        });
+        Ok(format!(
+            "parsed day {:02} `{}` ({}) in {}",
+            day,
+            name,
+            solution.title(),
+            format_duration(elapsed)
+        ))
+    }
+}
+
+impl Session {
+    fn eval(&self, loader: &InputLoader, command: &str, args: &[&str]) -> Result<String, String> {
+        let day = self.solution.day();
+        match (command, args) {
+            ("show", []) => Ok(self.solution.show(self.parsed.as_ref())),
+            ("part1" | "part2", []) => {
+                let start = Instant::now();
+                let answer = if command == "part1" {
+                    self.solution.part1(self.parsed.as_ref())
+                } else {
+                    self.solution.part2(self.parsed.as_ref())
+                };
+                Ok(format!("{} ({})", answer, format_duration(start.elapsed())))
+            }
+            ("inputs", []) => {
+                let mut names = loader.discover(day);
+                let examples = examples::load(day).map_err(|e| e.to_string())?;
+                names.extend(examples.into_iter().map(|example| example.name));
+                Ok(names.join("\n"))
+            }
+            ("show" | "part1" | "part2" | "inputs", _) => Err(format!("usage: {}", command)),
+            _ => match explorer(day) {
+                Some(explorer) => explorer.explore(self.parsed.as_ref(), command, args),
+                None => Err(format!("unknown command `{}`", command)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repl(day: u8, input: &str) -> Repl {
+        let mut repl = Repl::new(InputLoader::default());
+        repl.eval(&format!("load {} {}", day, input)).unwrap();
+        repl
+    }
+
+    #[test]
+    fn test_repl() {
+        let mut repl = Repl::new(InputLoader::default());
+        assert_eq!(repl.prompt(), "aoc> ");
+        assert!(repl.eval("show").unwrap_err().contains("no day loaded"));
+        assert!(repl.eval("load 99").is_err());
+        assert!(repl.eval("load 1 nobody").unwrap_err().contains("nobody"));
+
+        repl.eval("load 1 sample").unwrap();
+        assert_eq!(repl.prompt(), "day 01 (sample)> ");
+        assert!(repl.eval("part1").unwrap().starts_with("7 ("));
+        assert!(repl.eval("show").unwrap().contains("199"));
+        assert!(repl.eval("help").unwrap().contains("window <SIZE>"));
+        assert!(repl.eval("inputs").unwrap().contains("default\n"));
+        assert_eq!(repl.eval("").unwrap(), "");
+        assert_eq!(
+            repl.eval("frobnicate").unwrap_err(),
+            "unknown command `frobnicate`"
+        );
+    }
+
+    #[test]
+    fn test_explore() {
+        let mut day01 = repl(1, "sample");
+        assert_eq!(day01.eval("window 1").unwrap(), "7");
+        assert_eq!(day01.eval("window 3").unwrap(), "5");
+        assert!(day01.eval("window 0").is_err());
+        assert_eq!(day01.eval("window").unwrap_err(), "usage: window <SIZE>");
+
+        let mut day02 = repl(2, "sample");
+        let step = day02.eval("step 3").unwrap();
+        assert!(step.contains("part 1: horizontal 13, depth 5"), "{}", step);
+
+        let mut day03 = repl(3, "sample");
+        assert_eq!(day03.eval("gamma").unwrap(), "10110 (22)");
+        assert_eq!(day03.eval("epsilon").unwrap(), "01001 (9)");
+        assert!(day03.eval("rating oxygen").unwrap().ends_with("10111 (23)"));
+        assert!(day03.eval("rating co2").unwrap().ends_with("01010 (10)"));
+        assert_eq!(day03.eval("gamma 1").unwrap_err(), "usage: gamma");
+
+        let mut day04 = repl(4, "sample");
+        let play = day04.eval("play 12").unwrap();
+        assert!(play.contains("board 2 wins on 24, score 4512"), "{}", play);
+        assert!(day04
+            .eval("board 2 12")
+            .unwrap()
+            .starts_with("[14][21][17][24][ 4]"));
+        assert!(day04.eval("board 3").is_err());
+
+        let mut day05 = repl(5, "sample");
+        assert!(day05.eval("types").unwrap().contains("diagonal   4"));
+        assert_eq!(day05.eval("crosses horizontal vertical").unwrap(), "5");
+        assert!(day05.eval("diagram").unwrap().starts_with("1.1....11."));
+        assert!(day05.eval("lines sideways").is_err());
+    }
+}
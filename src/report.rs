@@ -0,0 +1,267 @@
+use std::fmt::Write;
+
+use crate::alloc_stats::AllocStats;
+use crate::verify::{Check, Status};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("unknown format `{}`, expected `json` or `csv`", s)),
+        }
+    }
+}
+
+pub fn render(format: Format, checks: &[Check]) -> String {
+    match format {
+        Format::Json => to_json(checks),
+        Format::Csv => to_csv(checks),
+    }
+}
+
+fn status_name(status: Status) -> &'static str {
+    match status {
+        Status::Pass => "pass",
+        Status::Fail => "fail",
+        Status::Unrecorded => "unrecorded",
+        Status::Error => "error",
+    }
+}
+
+/// An array with one object per check. Answers are strings so wide integers survive.
+pub fn to_json(checks: &[Check]) -> String {
+    let mut json = String::from("[");
+    for (idx, check) in checks.iter().enumerate() {
+        if idx > 0 {
+            json.push(',');
+        }
+        let (answer, error) = match &check.actual {
+            Ok(answer) => (json_string(&answer.to_string()), "null".to_string()),
+            Err(e) => ("null".to_string(), json_string(e)),
+        };
+        let expected = check
+            .expected
+            .as_ref()
+            .map_or("null".to_string(), |e| json_string(&e.to_string()));
+        write!(
+            json,
+            "\n  {{\"day\": {}, \"part\": {}, \"input\": {}, \"answer\": {}, \"expected\": {}, \
+             \"status\": \"{}\", \"error\": {}, \"parse_ns\": {}, \"solve_ns\": {}, \
+             \"parse_alloc\": {}, \"solve_alloc\": {}}}",
+            check.day,
+            check.part,
+            json_string(&check.input),
+            answer,
+            expected,
+            status_name(check.status()),
+            error,
+            check.parse_time.as_nanos(),
+            check.solve_time.as_nanos(),
+            json_alloc(check.parse_alloc),
+            json_alloc(check.solve_alloc)
+        )
+        .unwrap();
+    }
+    json.push_str("\n]\n");
+    json
+}
+
+pub fn to_csv(checks: &[Check]) -> String {
+    let mut csv = String::from(
+        "day,part,input,answer,expected,status,error,parse_ns,solve_ns,\
+         parse_allocs,parse_bytes,parse_peak,solve_allocs,solve_bytes,solve_peak\n",
+    );
+    for check in checks {
+        let (answer, error) = match &check.actual {
+            Ok(answer) => (answer.to_string(), String::new()),
+            Err(e) => (String::new(), e.clone()),
+        };
+        let expected = check
+            .expected
+            .as_ref()
+            .map_or(String::new(), |e| e.to_string());
+        writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            check.day,
+            check.part,
+            csv_field(&check.input),
+            csv_field(&answer),
+            csv_field(&expected),
+            status_name(check.status()),
+            csv_field(&error),
+            check.parse_time.as_nanos(),
+            check.solve_time.as_nanos(),
+            csv_alloc(check.parse_alloc),
+            csv_alloc(check.solve_alloc)
+        )
+        .unwrap();
+    }
+    csv
+}
+
+/// Splits CSV text as written by `to_csv` into rows of fields, header included.
+pub fn read_csv(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut chars = text.chars().peekable();
+    let mut quoted = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted field".to_string());
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn json_alloc(stats: Option<AllocStats>) -> String {
+    stats.map_or("null".to_string(), |s| {
+        format!(
+            "{{\"allocs\": {}, \"bytes\": {}, \"peak\": {}}}",
+            s.allocs, s.bytes, s.peak
+        )
+    })
+}
+
+/// Three fields, empty when allocations were not counted.
+fn csv_alloc(stats: Option<AllocStats>) -> String {
+    stats.map_or(",,".to_string(), |s| {
+        format!("{},{},{}", s.allocs, s.bytes, s.peak)
+    })
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Answer;
+    use std::time::Duration;
+
+    fn checks() -> Vec<Check> {
+        vec![
+            Check {
+                day: 5,
+                part: 2,
+                input: "default".to_string(),
+                expected: Some(Answer::from(19571u32)),
+                actual: Ok(Answer::from(19571u32)),
+                parse_time: Duration::from_micros(80),
+                solve_time: Duration::from_millis(18),
+                parse_alloc: None,
+                solve_alloc: Some(AllocStats {
+                    allocs: 3,
+                    bytes: 4096,
+                    peak: 2048,
+                }),
+            },
+            Check {
+                day: 2,
+                part: 1,
+                input: "bob, jr".to_string(),
+                expected: None,
+                actual: Err("missing \"number\"\nat line 2".to_string()),
+                parse_time: Duration::from_nanos(5),
+                solve_time: Duration::ZERO,
+                parse_alloc: None,
+                solve_alloc: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_json() {
+        let json = to_json(&checks());
+        assert_eq!(
+            json,
+            r#"[
+  {"day": 5, "part": 2, "input": "default", "answer": "19571", "expected": "19571", "status": "pass", "error": null, "parse_ns": 80000, "solve_ns": 18000000, "parse_alloc": null, "solve_alloc": {"allocs": 3, "bytes": 4096, "peak": 2048}},
+  {"day": 2, "part": 1, "input": "bob, jr", "answer": null, "expected": null, "status": "error", "error": "missing \"number\"\nat line 2", "parse_ns": 5, "solve_ns": 0, "parse_alloc": null, "solve_alloc": null}
+]
+"#
+        );
+        assert_eq!(to_json(&[]), "[\n]\n");
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = to_csv(&checks());
+        assert_eq!(
+            csv,
+            "day,part,input,answer,expected,status,error,parse_ns,solve_ns,\
+             parse_allocs,parse_bytes,parse_peak,solve_allocs,solve_bytes,solve_peak
+5,2,default,19571,19571,pass,,80000,18000000,,,,3,4096,2048
+2,1,\"bob, jr\",,,error,\"missing \"\"number\"\"\nat line 2\",5,0,,,,,,
+"
+        );
+    }
+
+    #[test]
+    fn test_read_csv() {
+        let rows = read_csv(&to_csv(&checks())).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0][0], "day");
+        assert_eq!(rows[2][2], "bob, jr");
+        assert_eq!(rows[2][6], "missing \"number\"\nat line 2");
+        assert!(rows.iter().all(|row| row.len() == 15));
+        assert!(read_csv("a,\"b").is_err());
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,243 @@
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use crate::alloc_stats::{self, AllocStats};
+use crate::parallel;
+use crate::trace::{self, Event};
+use crate::{Answer, DynSolution, ParseError};
+
+pub(crate) const PANICKED: &str = "panicked: ";
+
+/// Why a day or one of its parts has no answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// The input could not be read.
+    Input(String),
+    Parse(ParseError),
+    /// The solver panicked, with the panic's message.
+    Panic(String),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Input(message) => write!(f, "{}", message),
+            Failure::Parse(error) => write!(f, "{}", error),
+            Failure::Panic(message) => write!(f, "{}{}", PANICKED, message),
+        }
+    }
+}
+
+/// A day to run against one input.
+pub struct Job<'a> {
+    pub day: &'a dyn DynSolution,
+    /// Name of the input, such as `default` or a user.
+    pub name: String,
+    pub input: Result<String, String>,
+    pub parts: Vec<u8>,
+}
+
+/// The outcome of a `Job`.
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub day: u8,
+    pub name: String,
+    pub parse_time: Duration,
+    /// What parsing allocated, when built with the `alloc-stats` feature.
+    pub parse_alloc: Option<AllocStats>,
+    pub parsed: Result<(), Failure>,
+    /// One per requested part; a part fails with the parse failure if parsing did.
+    pub parts: Vec<PartRun>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartRun {
+    pub part: u8,
+    pub answer: Result<Answer, Failure>,
+    pub time: Duration,
+    pub alloc: Option<AllocStats>,
+    /// Trace events the part emitted, if the runner explains.
+    pub events: Vec<Event>,
+}
+
+/// Runs jobs on a pool of threads: every input is parsed concurrently, then every part of
+/// every parsed input is solved concurrently. Results come back in the order of the jobs.
+#[derive(Debug, Clone)]
+pub struct Runner {
+    jobs: usize,
+    explain: bool,
+}
+
+impl Runner {
+    /// A runner using one thread per core.
+    pub fn new() -> Self {
+        Self {
+            jobs: parallel::default_jobs(),
+            explain: false,
+        }
+    }
+
+    /// Limits how many threads run at once.
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    /// Collects the trace events of every part.
+    pub fn explain(mut self, explain: bool) -> Self {
+        self.explain = explain;
+        self
+    }
+
+    pub fn run(&self, jobs: &[Job]) -> Vec<Run> {
+        let parsed = parallel::map(self.jobs, jobs, |job| {
+            let input = match &job.input {
+                Ok(input) => input,
+                Err(message) => {
+                    return (Err(Failure::Input(message.clone())), Duration::ZERO, None)
+                }
+            };
+            let start = Instant::now();
+            let (parsed, alloc) = alloc_stats::measure(|| catch(|| job.day.parse(input)));
+            let parsed = parsed.and_then(|r| r.map_err(Failure::Parse));
+            (parsed, start.elapsed(), alloc)
+        });
+
+        let tasks = jobs
+            .iter()
+            .zip(&parsed)
+            .flat_map(|(job, (parsed, _, _))| {
+                job.parts.iter().map(move |&part| (job.day, parsed, part))
+            })
+            .collect::<Vec<_>>();
+        let mut parts = parallel::map(self.jobs, &tasks, |&(day, parsed, part)| {
+            let parsed = match parsed {
+                Ok(parsed) => parsed,
+                Err(failure) => {
+                    return PartRun {
+                        part,
+                        answer: Err(failure.clone()),
+                        time: Duration::ZERO,
+                        alloc: None,
+                        events: Vec::new(),
+                    }
+                }
+            };
+
+            let solve = || match part {
+                1 => day.part1(parsed.as_ref()),
+                _ => day.part2(parsed.as_ref()),
+            };
+            let start = Instant::now();
+            let ((answer, alloc), events) = if self.explain {
+                trace::collect(|| alloc_stats::measure(|| catch(solve)))
+            } else {
+                (alloc_stats::measure(|| catch(solve)), Vec::new())
+            };
+            PartRun {
+                part,
+                answer,
+                time: start.elapsed(),
+                alloc,
+                events,
+            }
+        })
+        .into_iter();
+
+        jobs.iter()
+            .zip(parsed)
+            .map(|(job, (parsed, parse_time, parse_alloc))| Run {
+                day: job.day.day(),
+                name: job.name.clone(),
+                parse_time,
+                parse_alloc,
+                parsed: parsed.map(|_| ()),
+                parts: parts.by_ref().take(job.parts.len()).collect(),
+            })
+            .collect()
+    }
+}
+
+impl Default for Runner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runs `f`, turning a panic into a failure carrying its message.
+fn catch<T>(f: impl FnOnce() -> T) -> Result<T, Failure> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown cause".to_string());
+        Failure::Panic(message)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{examples, solution, SOLUTIONS};
+
+    fn jobs() -> Vec<Job<'static>> {
+        SOLUTIONS
+            .iter()
+            .map(|&day| Job {
+                day,
+                name: "sample".to_string(),
+                input: Ok(examples::input(day.day(), "sample")),
+                parts: vec![1, 2],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_run() {
+        let sequential = Runner::new().jobs(1).run(&jobs());
+        let parallel = Runner::new().jobs(4).run(&jobs());
+        assert_eq!(parallel.len(), SOLUTIONS.len());
+        for (a, b) in sequential.iter().zip(&parallel) {
+            assert_eq!((a.day, &a.name), (b.day, &b.name));
+            let answers = |run: &Run| {
+                run.parts
+                    .iter()
+                    .map(|p| (p.part, p.answer.clone()))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(answers(a), answers(b));
+        }
+        assert_eq!(parallel[3].parts[1].answer, Ok(1924u64.into()));
+    }
+
+    #[test]
+    fn test_failures() {
+        let day = solution(1).unwrap();
+        let job = |input: Result<&str, &str>, parts: Vec<u8>| Job {
+            day,
+            name: "broken".to_string(),
+            input: input.map(str::to_string).map_err(str::to_string),
+            parts,
+        };
+        let runs = Runner::new().explain(true).run(&[
+            job(Err("no such input"), vec![1, 2]),
+            job(Ok("1\nx"), vec![2]),
+            job(Ok("1\n2"), vec![1]),
+        ]);
+
+        assert_eq!(
+            runs[0].parsed,
+            Err(Failure::Input("no such input".to_string()))
+        );
+        assert_eq!(runs[0].parts.len(), 2);
+        assert!(matches!(runs[1].parts[0].answer, Err(Failure::Parse(_))));
+        assert_eq!(runs[1].parts[0].part, 2);
+        assert_eq!(runs[2].parts[0].answer, Ok(1u32.into()));
+        assert_eq!(
+            Failure::Panic("boom".to_string()).to_string(),
+            "panicked: boom"
+        );
+    }
+}
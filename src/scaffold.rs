@@ -0,0 +1,264 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    AlreadyExists(u8),
+    InvalidDay(u8),
+    Io(PathBuf, io::Error),
+    /// `lib.rs` no longer has the shape the scaffolder knows how to extend.
+    Unregistrable(String),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::AlreadyExists(day) => write!(f, "day {} already exists", day),
+            ScaffoldError::InvalidDay(day) => {
+                write!(f, "day must be between 1 and 25, got {}", day)
+            }
+            ScaffoldError::Io(path, e) => write!(f, "failed to access `{}`: {}", path.display(), e),
+            ScaffoldError::Unregistrable(reason) => {
+                write!(f, "cannot register day in lib.rs: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+pub fn crate_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Creates `src/day_XX.rs`, its input and example files, and registers it in `src/lib.rs`.
+///
+/// Input and example files that already exist are left alone; the module itself must not.
+/// Returns the files written.
+pub fn new_day(root: &Path, day: u8, title: &str) -> Result<Vec<PathBuf>, ScaffoldError> {
+    if !(1..=25).contains(&day) {
+        return Err(ScaffoldError::InvalidDay(day));
+    }
+
+    let module = root.join("src").join(format!("day_{:02}.rs", day));
+    let lib = root.join("src").join("lib.rs");
+    let lib_source = read(&lib)?;
+    if module.exists() {
+        return Err(ScaffoldError::AlreadyExists(day));
+    }
+    let lib_source = register(&lib_source, day)?;
+
+    let examples = root
+        .join("input")
+        .join("examples")
+        .join(format!("day_{:02}", day));
+    let files = [
+        (module, module_source(day, title)),
+        (
+            root.join("input").join(format!("day_{:02}.txt", day)),
+            String::new(),
+        ),
+        (examples.join("sample.txt"), String::new()),
+        (
+            examples.join("answers.toml"),
+            format!(
+                "# Answers given in the puzzle text for its examples.\n\n[day_{:02}.sample]\n",
+                day
+            ),
+        ),
+    ];
+
+    let mut written = Vec::new();
+    for (path, contents) in files {
+        if path.exists() {
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| ScaffoldError::Io(parent.to_path_buf(), e))?;
+        }
+        std::fs::write(&path, contents).map_err(|e| ScaffoldError::Io(path.clone(), e))?;
+        written.push(path);
+    }
+
+    std::fs::write(&lib, lib_source).map_err(|e| ScaffoldError::Io(lib.clone(), e))?;
+    written.push(lib);
+    Ok(written)
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    std::fs::read_to_string(path).map_err(|e| ScaffoldError::Io(path.to_path_buf(), e))
+}
+
+/// Adds `pub mod day_XX;` and the `SOLUTIONS` entry to `lib.rs`, keeping both in day order.
+pub fn register(lib: &str, day: u8) -> Result<String, ScaffoldError> {
+    let mod_line = format!("pub mod day_{:02};", day);
+    let entry_line = format!("    &day_{:02}::Day{:02},", day, day);
+    if lib.lines().any(|line| line == mod_line) {
+        return Err(ScaffoldError::AlreadyExists(day));
+    }
+
+    let mut lines = lib.lines().map(str::to_string).collect::<Vec<_>>();
+    insert_sorted(&mut lines, &mod_line, |line| {
+        line.starts_with("pub mod day_")
+    })
+    .ok_or_else(|| ScaffoldError::Unregistrable("no `pub mod day_XX;` lines".to_string()))?;
+    insert_sorted(&mut lines, &entry_line, |line| {
+        line.starts_with("    &day_")
+    })
+    .ok_or_else(|| ScaffoldError::Unregistrable("no `SOLUTIONS` entries".to_string()))?;
+
+    let mut result = lines.join("\n");
+    if lib.ends_with('\n') {
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+/// Inserts `new` among the consecutive run of lines matching `is_peer`, in sorted position.
+fn insert_sorted(lines: &mut Vec<String>, new: &str, is_peer: impl Fn(&str) -> bool) -> Option<()> {
+    let first = lines.iter().position(|line| is_peer(line))?;
+    let mut idx = first;
+    while idx < lines.len() && is_peer(&lines[idx]) && lines[idx].as_str() < new {
+        idx += 1;
+    }
+    lines.insert(idx, new.to_string());
+    Some(())
+}
+
+fn module_source(day: u8, title: &str) -> String {
+    MODULE_TEMPLATE
+        .replace("{day}", &day.to_string())
+        .replace("{day:02}", &format!("{:02}", day))
+        .replace("{title}", &title.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+const MODULE_TEMPLATE: &str = r#"use crate::prelude::*;
+use crate::{normalize, Result, Solution};
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {
+    const DAY: u8 = {day};
+    const TITLE: &'static str = "{title}";
+
+    type Input = Vec<String>;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.lines().map(|line| line.trim().to_string()).collect())
+    }
+
+    fn part1(input: &Self::Input) -> u64 {
+        input.len() as u64
+    }
+
+    fn part2(input: &Self::Input) -> u64 {
+        input.len() as u64
+    }
+}
+
+pub fn solve_part1(input: &str) -> Result<u64> {
+    Ok(Day{day:02}::part1(&normalize::parse::<Day{day:02}>(input)?))
+}
+
+pub fn solve_part2(input: &str) -> Result<u64> {
+    Ok(Day{day:02}::part2(&normalize::parse::<Day{day:02}>(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn test_parse() {
+        let sample = examples::input({day}, "sample");
+        let input = Day{day:02}::parse(&sample).unwrap();
+        assert_eq!(input.len(), sample.lines().count());
+    }
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "pub mod answer;
+pub mod day_01;
+pub mod day_02;
+pub mod error;
+
+pub static SOLUTIONS: &[&dyn DynSolution] = &[
+    &day_01::Day01,
+    &day_02::Day02,
+];
+";
+
+    #[test]
+    fn test_register() {
+        let lib = register(LIB, 3).unwrap();
+        assert!(lib.contains("pub mod day_02;\npub mod day_03;\npub mod error;\n"));
+        assert!(lib.contains("    &day_02::Day02,\n    &day_03::Day03,\n];\n"));
+
+        let lib = register(&lib, 10).unwrap();
+        assert!(lib.contains("pub mod day_03;\npub mod day_10;\npub mod error;\n"));
+        assert!(lib.contains("    &day_03::Day03,\n    &day_10::Day10,\n];\n"));
+
+        assert!(matches!(
+            register(LIB, 2),
+            Err(ScaffoldError::AlreadyExists(2))
+        ));
+    }
+
+    #[test]
+    fn test_module_source() {
+        let source = module_source(6, "Lanternfish");
+        assert!(source.contains("pub struct Day06;"));
+        assert!(source.contains("    const DAY: u8 = 6;"));
+        assert!(source.contains("    const TITLE: &'static str = \"Lanternfish\";"));
+        assert!(source.contains("examples::input(6, \"sample\")"));
+        assert!(!source.contains("{day") && !source.contains("{title}"));
+    }
+
+    /// Removes the directory when dropped, so a failing test does not leave it behind.
+    struct TempDir(PathBuf);
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_new_day() {
+        let dir =
+            TempDir(std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id())));
+        let root = &dir.0;
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("input")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), LIB).unwrap();
+        std::fs::write(root.join("input/day_03.txt"), "downloaded").unwrap();
+
+        let written = new_day(root, 3, "Binary Diagnostic").unwrap();
+        assert!(written.contains(&root.join("src/day_03.rs")));
+        assert!(written.contains(&root.join("input/examples/day_03/sample.txt")));
+        assert_eq!(
+            std::fs::read_to_string(root.join("input/day_03.txt")).unwrap(),
+            "downloaded"
+        );
+        assert!(std::fs::read_to_string(root.join("src/lib.rs"))
+            .unwrap()
+            .contains("pub mod day_03;"));
+
+        assert!(matches!(
+            new_day(root, 3, "again"),
+            Err(ScaffoldError::AlreadyExists(3))
+        ));
+        assert!(matches!(
+            new_day(root, 26, ""),
+            Err(ScaffoldError::InvalidDay(26))
+        ));
+    }
+}
@@ -0,0 +1,448 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::client::{Client, ClientError};
+use crate::http::HttpBackend;
+use crate::Answer;
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Submitted during a cooldown; nothing was checked.
+    Wait,
+    /// The part was already solved, or its first part is not yet.
+    WrongLevel,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Outcome::Correct => "correct",
+            Outcome::Wrong => "wrong",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::Wait => "wait",
+            Outcome::WrongLevel => "wrong-level",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl std::str::FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "wrong" => Ok(Outcome::Wrong),
+            "too-high" => Ok(Outcome::TooHigh),
+            "too-low" => Ok(Outcome::TooLow),
+            "wait" => Ok(Outcome::Wait),
+            "wrong-level" => Ok(Outcome::WrongLevel),
+            _ => Err(format!("unknown outcome `{}`", s)),
+        }
+    }
+}
+
+/// Reads the outcome and any cooldown the site asked for out of its answer page.
+pub fn parse_response(body: &str) -> Option<(Outcome, Option<Duration>)> {
+    let outcome = if body.contains("That's the right answer") {
+        Outcome::Correct
+    } else if body.contains("That's not the right answer") {
+        if body.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if body.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if body.contains("You gave an answer too recently") {
+        Outcome::Wait
+    } else if body.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        return None;
+    };
+    Some((outcome, cooldown(body)))
+}
+
+/// "You have 1m 20s left to wait" or "please wait 5 minutes before trying again".
+fn cooldown(body: &str) -> Option<Duration> {
+    let body = body.to_lowercase();
+    if let Some(end) = body.find(" left to wait") {
+        let start = body[..end].rfind("you have ")? + "you have ".len();
+        let mut secs = 0;
+        for part in body[start..end].split_whitespace() {
+            let (number, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let number = number.parse::<u64>().ok()?;
+            secs += match unit {
+                "h" => number * 3600,
+                "m" => number * 60,
+                "s" => number,
+                _ => return None,
+            };
+        }
+        return Some(Duration::from_secs(secs));
+    }
+
+    let idx = body.find("please wait ")?;
+    let minutes = match body[idx + "please wait ".len()..]
+        .split_whitespace()
+        .next()?
+    {
+        "one" => 1,
+        number => number.parse().ok()?,
+    };
+    Some(Duration::from_secs(minutes * 60))
+}
+
+/// One line of submission history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    /// Seconds since the unix epoch.
+    pub time: u64,
+    pub input: String,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// No answer may be submitted for this input before this time.
+    pub wait_until: u64,
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    AlreadySolved(String),
+    /// The same answer, or one the site's hints already rule out, was submitted before.
+    Rejected {
+        previous: String,
+        outcome: Outcome,
+    },
+    Cooldown(Duration),
+    UnknownResponse(String),
+    Client(ClientError),
+    History(PathBuf, String),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::AlreadySolved(answer) => write!(f, "already solved with {}", answer),
+            SubmitError::Rejected { previous, outcome } => {
+                write!(f, "not submitting: {} was already {}", previous, outcome)
+            }
+            SubmitError::Cooldown(remaining) => {
+                write!(
+                    f,
+                    "still cooling down, {}s left to wait",
+                    remaining.as_secs()
+                )
+            }
+            SubmitError::UnknownResponse(body) => {
+                let body = body.trim();
+                write!(
+                    f,
+                    "unrecognised response: {}",
+                    body.get(..200).unwrap_or(body)
+                )
+            }
+            SubmitError::Client(e) => write!(f, "{}", e),
+            SubmitError::History(path, e) => {
+                write!(f, "submission history `{}`: {}", path.display(), e)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+impl From<ClientError> for SubmitError {
+    fn from(error: ClientError) -> Self {
+        SubmitError::Client(error)
+    }
+}
+
+/// Every answer submitted so far, stored as tab separated lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct History {
+    submissions: Vec<Submission>,
+}
+
+impl History {
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(".aoc-history")
+    }
+
+    /// Reads the history, treating a missing file as an empty one.
+    pub fn load(path: &Path) -> Result<Self, SubmitError> {
+        let error = |e: String| SubmitError::History(path.to_path_buf(), e);
+        match std::fs::read_to_string(path) {
+            Ok(text) => text.parse().map_err(error),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(error(e.to_string())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SubmitError> {
+        std::fs::write(path, self.to_string())
+            .map_err(|e| SubmitError::History(path.to_path_buf(), e.to_string()))
+    }
+
+    pub fn submissions(&self) -> &[Submission] {
+        &self.submissions
+    }
+
+    pub fn push(&mut self, submission: Submission) {
+        self.submissions.push(submission);
+    }
+
+    /// Why `answer` should not be submitted at `now`, if it should not.
+    pub fn check(
+        &self,
+        input: &str,
+        day: u8,
+        part: u8,
+        answer: &Answer,
+        now: u64,
+    ) -> Result<(), SubmitError> {
+        let wait_until = self
+            .submissions
+            .iter()
+            .filter(|s| s.input == input)
+            .map(|s| s.wait_until)
+            .max()
+            .unwrap_or(0);
+        if now < wait_until {
+            return Err(SubmitError::Cooldown(Duration::from_secs(wait_until - now)));
+        }
+
+        let same_part = self
+            .submissions
+            .iter()
+            .filter(|s| s.input == input && s.day == day && s.part == part);
+        for previous in same_part {
+            let previous_answer = previous.answer.parse::<Answer>().unwrap();
+            if previous.outcome == Outcome::Correct {
+                return Err(SubmitError::AlreadySolved(previous.answer.clone()));
+            }
+
+            let ruled_out = match previous.outcome {
+                Outcome::Wrong => *answer == previous_answer,
+                Outcome::TooHigh => at_least(answer, &previous_answer),
+                Outcome::TooLow => at_least(&previous_answer, answer),
+                _ => false,
+            };
+            if ruled_out {
+                return Err(SubmitError::Rejected {
+                    previous: previous.answer.clone(),
+                    outcome: previous.outcome,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn at_least(a: &Answer, b: &Answer) -> bool {
+    match (a.to_string().parse::<i128>(), b.to_string().parse::<i128>()) {
+        (Ok(a), Ok(b)) => a >= b,
+        _ => a == b,
+    }
+}
+
+impl std::str::FromStr for History {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut history = History::default();
+        for (idx, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || format!("line {} is invalid: `{}`", idx + 1, line);
+            let fields = line.split('\t').collect::<Vec<_>>();
+            let [time, input, day, part, answer, outcome, wait_until] = fields[..] else {
+                return Err(invalid());
+            };
+            history.push(Submission {
+                time: time.parse().map_err(|_| invalid())?,
+                input: input.to_string(),
+                day: day.parse().map_err(|_| invalid())?,
+                part: part.parse().map_err(|_| invalid())?,
+                answer: answer.to_string(),
+                outcome: outcome.parse()?,
+                wait_until: wait_until.parse().map_err(|_| invalid())?,
+            });
+        }
+        Ok(history)
+    }
+}
+
+impl fmt::Display for History {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# time\tinput\tday\tpart\tanswer\toutcome\twait_until")?;
+        for s in &self.submissions {
+            writeln!(
+                f,
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                s.time, s.input, s.day, s.part, s.answer, s.outcome, s.wait_until
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Posts `answer` unless the history says it is pointless or too soon, and records the outcome.
+pub fn submit<B: HttpBackend>(
+    client: &Client<B>,
+    history: &mut History,
+    input: &str,
+    day: u8,
+    part: u8,
+    answer: &Answer,
+    now: u64,
+) -> Result<Outcome, SubmitError> {
+    history.check(input, day, part, answer, now)?;
+
+    let body = client.post_answer(day, part, answer)?;
+    let (outcome, cooldown) =
+        parse_response(&body).ok_or_else(|| SubmitError::UnknownResponse(body.clone()))?;
+    history.push(Submission {
+        time: now,
+        input: input.to_string(),
+        day,
+        part,
+        answer: answer.to_string(),
+        outcome,
+        wait_until: now + cooldown.map_or(0, |d| d.as_secs()),
+    });
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::http::tests::serve;
+
+    const RIGHT: &str =
+        "<article><p>That's the right answer! You are one gold star closer.</p></article>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. \
+        Please wait one minute before trying again.</p></article>";
+    const TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait after \
+        submitting an answer before trying again.  You have 1m 20s left to wait.</p></article>";
+
+    fn client(url: &str) -> Client {
+        Client::from_config(&Config {
+            session: Some("s3cr3t".to_string()),
+            base_url: url.to_string(),
+            year: 2021,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(parse_response(RIGHT), Some((Outcome::Correct, None)));
+        assert_eq!(
+            parse_response(TOO_HIGH),
+            Some((Outcome::TooHigh, Some(Duration::from_secs(60))))
+        );
+        assert_eq!(
+            parse_response(TOO_RECENT),
+            Some((Outcome::Wait, Some(Duration::from_secs(80))))
+        );
+        assert_eq!(
+            parse_response(
+                "That's not the right answer. please wait 5 minutes before trying again."
+            ),
+            Some((Outcome::Wrong, Some(Duration::from_secs(300))))
+        );
+        assert_eq!(
+            parse_response("You don't seem to be solving the right level."),
+            Some((Outcome::WrongLevel, None))
+        );
+        assert_eq!(parse_response("<html>login</html>"), None);
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let mut history = History::default();
+        history.push(Submission {
+            time: 100,
+            input: "default".to_string(),
+            day: 5,
+            part: 2,
+            answer: "19571".to_string(),
+            outcome: Outcome::TooLow,
+            wait_until: 160,
+        });
+        assert_eq!(history.to_string().parse::<History>().unwrap(), history);
+        assert!("1\tdefault\t5".parse::<History>().is_err());
+    }
+
+    #[test]
+    fn test_submit() {
+        let (url, received) = serve(vec![
+            (200, TOO_HIGH.to_string()),
+            (200, TOO_RECENT.to_string()),
+            (200, RIGHT.to_string()),
+        ]);
+        let client = client(&url);
+        let mut history = History::default();
+        let submit = |history: &mut History, answer: u32, now| {
+            submit(
+                &client,
+                history,
+                "default",
+                1,
+                2,
+                &Answer::from(answer),
+                now,
+            )
+        };
+
+        assert_eq!(submit(&mut history, 2000, 1000).unwrap(), Outcome::TooHigh);
+        assert!(received.lock().unwrap()[0].starts_with("POST /2021/day/1/answer HTTP/1.1\r\n"));
+        assert!(received.lock().unwrap()[0].ends_with("level=2&answer=2000"));
+
+        // inside the minute the site asked for
+        assert!(
+            matches!(submit(&mut history, 1150, 1030), Err(SubmitError::Cooldown(d)) if d.as_secs() == 30)
+        );
+        // ruled out by the earlier hint, never sent
+        assert!(matches!(
+            submit(&mut history, 2000, 1100),
+            Err(SubmitError::Rejected {
+                outcome: Outcome::TooHigh,
+                ..
+            })
+        ));
+        assert!(matches!(
+            submit(&mut history, 2500, 1100),
+            Err(SubmitError::Rejected { .. })
+        ));
+
+        assert_eq!(submit(&mut history, 1150, 1100).unwrap(), Outcome::Wait);
+        assert_eq!(history.submissions()[1].wait_until, 1180);
+        assert_eq!(submit(&mut history, 1150, 1180).unwrap(), Outcome::Correct);
+        assert!(matches!(
+            submit(&mut history, 1150, 2000),
+            Err(SubmitError::AlreadySolved(_))
+        ));
+
+        assert_eq!(received.lock().unwrap().len(), 3);
+    }
+}
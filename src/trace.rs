@@ -0,0 +1,144 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::cell::RefCell;
+
+use crate::prelude::*;
+
+/// Something a solver did on its way to an answer, such as a bingo board winning.
+///
+/// Events are emitted with the `trace!` macro and go nowhere unless a sink is installed
+/// for the current thread with `with_sink` or `collect`. Without `std` there are no sinks
+/// and events are never built.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub name: &'static str,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+impl Event {
+    pub fn field(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        for (key, value) in &self.fields {
+            write!(f, " {}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+type Sink = Box<dyn FnMut(&Event)>;
+
+#[cfg(feature = "std")]
+thread_local! {
+    static SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+}
+
+/// Emits an event named by a string literal with `key = value` fields, values being
+/// anything `Display`. Fields are only formatted when a sink is installed.
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use advent_of_code_2021::{trace, trace::collect};
+///
+/// let (_, events) = collect(|| trace!("board won", board = 2, draw = 24));
+/// assert_eq!(events[0].to_string(), "board won board=2 draw=24");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! trace {
+    ($name:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        if $crate::trace::enabled() {
+            $crate::trace::emit($crate::trace::Event {
+                name: $name,
+                fields: vec![$((stringify!($key), $value.to_string())),*],
+            });
+        }
+    };
+}
+
+/// Whether events emitted on this thread go anywhere.
+#[cfg(feature = "std")]
+pub fn enabled() -> bool {
+    SINK.with(|sink| sink.borrow().is_some())
+}
+
+#[cfg(not(feature = "std"))]
+pub fn enabled() -> bool {
+    false
+}
+
+#[cfg(feature = "std")]
+pub fn emit(event: Event) {
+    SINK.with(|sink| {
+        if let Some(sink) = sink.borrow_mut().as_mut() {
+            sink(&event);
+        }
+    });
+}
+
+#[cfg(not(feature = "std"))]
+pub fn emit(_event: Event) {}
+
+/// Runs `f` with every event emitted on this thread passed to `sink`.
+#[cfg(feature = "std")]
+pub fn with_sink<T>(sink: impl FnMut(&Event) + 'static, f: impl FnOnce() -> T) -> T {
+    /// Puts the previous sink back even if `f` panics.
+    struct Restore(Option<Sink>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            SINK.with(|sink| *sink.borrow_mut() = previous);
+        }
+    }
+
+    let previous = SINK.with(|current| current.borrow_mut().replace(Box::new(sink)));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Runs `f`, returning its result along with the events it emitted.
+#[cfg(feature = "std")]
+pub fn collect<T>(f: impl FnOnce() -> T) -> (T, Vec<Event>) {
+    let events = std::rc::Rc::new(RefCell::new(Vec::new()));
+    let sink = events.clone();
+    let result = with_sink(move |event| sink.borrow_mut().push(event.clone()), f);
+    let events = events.take();
+    (result, events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_sink() {
+        trace!("nobody listening", value = 1);
+        assert!(!enabled());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_trace() {
+        let (answer, events) = collect(|| {
+            trace!("outer", depth = 1);
+            let (_, inner) = collect(|| trace!("inner"));
+            assert_eq!(inner.len(), 1);
+            42
+        });
+        assert_eq!(answer, 42);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].to_string(), "outer depth=1");
+        assert_eq!(events[0].field("depth"), Some("1"));
+        assert!(!enabled());
+    }
+}
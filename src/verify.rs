@@ -0,0 +1,197 @@
+use std::time::Duration;
+
+use crate::alloc_stats::AllocStats;
+use crate::input::InputLoader;
+use crate::manifest::Manifest;
+use crate::runner::{Job, Run, Runner, PANICKED};
+use crate::{Answer, DynSolution};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// Computed, but the manifest has no answer to compare against.
+    Unrecorded,
+    Error,
+}
+
+/// One part of one day run against one input, next to its expected answer.
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub expected: Option<Answer>,
+    pub actual: Result<Answer, String>,
+    /// Time spent parsing the input, shared by both parts.
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    /// Allocations while parsing and solving, when built with the `alloc-stats` feature.
+    pub parse_alloc: Option<AllocStats>,
+    pub solve_alloc: Option<AllocStats>,
+}
+
+impl Check {
+    pub fn status(&self) -> Status {
+        match (&self.actual, &self.expected) {
+            (Err(_), _) => Status::Error,
+            (Ok(_), None) => Status::Unrecorded,
+            (Ok(actual), Some(expected)) if actual == expected => Status::Pass,
+            (Ok(_), Some(_)) => Status::Fail,
+        }
+    }
+
+    /// Whether the solver panicked rather than failing to parse or giving an answer.
+    pub fn panicked(&self) -> bool {
+        matches!(&self.actual, Err(e) if e.starts_with(PANICKED))
+    }
+}
+
+/// Runs `day` against every input it has, and every input the manifest expects it to have.
+pub fn verify_day(day: &dyn DynSolution, loader: &InputLoader, manifest: &Manifest) -> Vec<Check> {
+    verify_days(&Runner::new().jobs(1), &[day], loader, manifest)
+}
+
+/// `verify_day` for several days, all run together by `runner`.
+pub fn verify_days(
+    runner: &Runner,
+    days: &[&dyn DynSolution],
+    loader: &InputLoader,
+    manifest: &Manifest,
+) -> Vec<Check> {
+    let mut jobs = Vec::new();
+    for &day in days {
+        let mut names = loader.discover(day.day());
+        for (_, name) in manifest.inputs().filter(|(d, _)| *d == day.day()) {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+
+        jobs.extend(names.into_iter().map(|name| {
+            Job {
+                day,
+                input: loader
+                    .named(&name)
+                    .load(day.day())
+                    .map_err(|e| e.to_string()),
+                name,
+                parts: vec![1, 2],
+            }
+        }));
+    }
+    check_jobs(runner, &jobs, manifest)
+}
+
+/// Runs `jobs`, comparing each answer with what the manifest expects for its input.
+pub fn check_jobs(runner: &Runner, jobs: &[Job], manifest: &Manifest) -> Vec<Check> {
+    runner
+        .run(jobs)
+        .iter()
+        .flat_map(|run| {
+            let expected = [1, 2].map(|part| manifest.expected(run.day, &run.name, part).cloned());
+            checks(run, expected)
+        })
+        .collect()
+}
+
+/// Parses `input` once and runs both parts on it, timing each phase. A panic in the solver
+/// becomes an error for the parts it affects.
+pub fn check_input(
+    day: &dyn DynSolution,
+    name: &str,
+    input: Result<String, String>,
+    expected: [Option<Answer>; 2],
+) -> Vec<Check> {
+    let job = Job {
+        day,
+        name: name.to_string(),
+        input,
+        parts: vec![1, 2],
+    };
+    let runs = Runner::new().jobs(1).run(&[job]);
+    checks(&runs[0], expected)
+}
+
+fn checks(run: &Run, expected: [Option<Answer>; 2]) -> Vec<Check> {
+    run.parts
+        .iter()
+        .map(|part| Check {
+            day: run.day,
+            part: part.part,
+            input: run.name.clone(),
+            expected: expected[usize::from(part.part) - 1].clone(),
+            actual: part.answer.clone().map_err(|e| e.to_string()),
+            parse_time: run.parse_time,
+            solve_time: part.time,
+            parse_alloc: run.parse_alloc,
+            solve_alloc: part.alloc,
+        })
+        .collect()
+}
+
+/// Records every computed answer in the manifest, replacing what it expected before.
+pub fn bless(manifest: &mut Manifest, checks: &[Check]) {
+    for check in checks {
+        if let Ok(answer) = &check.actual {
+            manifest.record(check.day, &check.input, check.part, answer.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SOLUTIONS;
+
+    #[test]
+    fn test_answers() {
+        let manifest = Manifest::load(&Manifest::default_path()).unwrap();
+        let loader = InputLoader::default();
+
+        let mut checked = 0;
+        for day in SOLUTIONS {
+            for check in verify_day(*day, &loader, &manifest) {
+                if check.expected.is_some() {
+                    assert_eq!(
+                        check.status(),
+                        Status::Pass,
+                        "day {} part {} on `{}`: expected {:?}, got {:?}",
+                        check.day,
+                        check.part,
+                        check.input,
+                        check.expected,
+                        check.actual
+                    );
+                    checked += 1;
+                }
+            }
+        }
+        assert!(checked >= 10);
+    }
+
+    #[test]
+    fn test_status() {
+        let mut manifest = "[day_01.default]\npart1 = 1\n".parse::<Manifest>().unwrap();
+        let loader = InputLoader::new("/nonexistent");
+        let checks = verify_day(crate::solution(1).unwrap(), &loader, &manifest);
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].status(), Status::Error);
+        assert_eq!(checks[0].expected, Some(Answer::from(1u32)));
+
+        let check = Check {
+            day: 1,
+            part: 2,
+            input: "alice".to_string(),
+            expected: None,
+            actual: Ok(Answer::from(7u32)),
+            parse_time: Duration::ZERO,
+            solve_time: Duration::ZERO,
+            parse_alloc: None,
+            solve_alloc: None,
+        };
+        assert_eq!(check.status(), Status::Unrecorded);
+        bless(&mut manifest, &[check]);
+        assert_eq!(manifest.expected(1, "alice", 2), Some(&Answer::from(7u32)));
+    }
+}
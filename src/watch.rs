@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::examples;
+use crate::report::read_csv;
+
+/// Files that affect a day's answers: its module, its input and its examples.
+pub fn watched_paths(root: &Path, input: &Path, day: u8) -> Vec<PathBuf> {
+    let mut paths = vec![
+        root.join("src").join(format!("day_{:02}.rs", day)),
+        input.to_path_buf(),
+    ];
+    let examples = examples::day_dir(day);
+    paths.push(examples.clone());
+    if let Ok(entries) = std::fs::read_dir(&examples) {
+        let mut files = entries.flatten().map(|e| e.path()).collect::<Vec<_>>();
+        files.sort();
+        paths.extend(files);
+    }
+    paths
+}
+
+/// Polls modification times and reports when any of them moved.
+#[derive(Debug, Default)]
+pub struct Watcher {
+    last: Option<Vec<(PathBuf, Option<SystemTime>)>>,
+}
+
+impl Watcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether `paths` changed since the previous call; the first call always reports a change.
+    pub fn changed(&mut self, paths: &[PathBuf]) -> bool {
+        let stamps = paths
+            .iter()
+            .map(|path| {
+                let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+                (path.clone(), modified)
+            })
+            .collect::<Vec<_>>();
+        let changed = self.last.as_ref() != Some(&stamps);
+        self.last = Some(stamps);
+        changed
+    }
+}
+
+/// Answers of one run keyed by `(input, part)`, errors included as text.
+pub type Snapshot = BTreeMap<(String, u8), String>;
+
+/// Reads a snapshot from the CSV the `run --format csv` command prints.
+pub fn snapshot_from_csv(csv: &str) -> Result<Snapshot, String> {
+    let rows = read_csv(csv)?;
+    let mut snapshot = Snapshot::new();
+    for row in rows.iter().skip(1) {
+        let [_, part, input, answer, _, status, error, ..] = &row[..] else {
+            return Err(format!("unexpected report row `{}`", row.join(",")));
+        };
+        let part = part
+            .parse()
+            .map_err(|_| format!("unexpected part `{}`", part))?;
+        let value = if status == "error" {
+            format!("error: {}", error.lines().next().unwrap_or(""))
+        } else {
+            answer.clone()
+        };
+        snapshot.insert((input.clone(), part), value);
+    }
+    Ok(snapshot)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub input: String,
+    pub part: u8,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// Entries whose answer differs between two runs, in key order.
+pub fn diff(before: &Snapshot, after: &Snapshot) -> Vec<Change> {
+    let mut keys = before.keys().chain(after.keys()).collect::<Vec<_>>();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .filter(|key| before.get(*key) != after.get(*key))
+        .map(|key| Change {
+            input: key.0.clone(),
+            part: key.1,
+            before: before.get(key).cloned(),
+            after: after.get(key).cloned(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::Duration;
+
+    #[test]
+    fn test_watcher() {
+        let path = std::env::temp_dir().join(format!("aoc-watch-{}.txt", std::process::id()));
+        std::fs::write(&path, "1").unwrap();
+        let paths = vec![path.clone()];
+
+        let mut watcher = Watcher::new();
+        assert!(watcher.changed(&paths));
+        assert!(!watcher.changed(&paths));
+
+        let later = SystemTime::now() + Duration::from_secs(10);
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert!(watcher.changed(&paths));
+        assert!(!watcher.changed(&paths));
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(watcher.changed(&paths));
+    }
+
+    #[test]
+    fn test_watched_paths() {
+        let paths = watched_paths(Path::new("/repo"), Path::new("/repo/input/day_05.txt"), 5);
+        assert_eq!(paths[0], Path::new("/repo/src/day_05.rs"));
+        assert_eq!(paths[1], Path::new("/repo/input/day_05.txt"));
+        assert!(paths.contains(&examples::day_dir(5).join("sample.txt")));
+    }
+
+    #[test]
+    fn test_diff() {
+        let csv = "day,part,input,answer,expected,status,error,parse_ns,solve_ns
+5,1,sample,5,5,pass,,1,1
+5,2,sample,12,12,pass,,1,1
+5,2,default,,,error,\"bad\nline\",1,1
+";
+        let before = snapshot_from_csv(csv).unwrap();
+        assert_eq!(before[&("default".to_string(), 2)], "error: bad");
+
+        let mut after = before.clone();
+        after.insert(("sample".to_string(), 2), "13".to_string());
+        after.remove(&("sample".to_string(), 1));
+        assert_eq!(
+            diff(&before, &after),
+            vec![
+                Change {
+                    input: "sample".to_string(),
+                    part: 1,
+                    before: Some("5".to_string()),
+                    after: None,
+                },
+                Change {
+                    input: "sample".to_string(),
+                    part: 2,
+                    before: Some("12".to_string()),
+                    after: Some("13".to_string()),
+                },
+            ]
+        );
+        assert!(diff(&before, &before).is_empty());
+    }
+}